@@ -0,0 +1,30 @@
+//! Criterion benchmarks of all solutions on their real inputs.
+//! Run `cargo bench` for all days, or `cargo bench -- 01` for a single day.
+//! Reports with confidence intervals are written to `target/criterion/report/index.html`.
+use std::hint::black_box;
+
+use advent_of_code::template::bench::Registry;
+use advent_of_code::Day;
+use criterion::Criterion;
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+struct CriterionRegistry<'a>(&'a mut Criterion);
+
+impl Registry for CriterionRegistry<'_> {
+    fn bench<I: Copy, T>(&mut self, day: Day, step: &str, input: I, func: impl Fn(I) -> T) {
+        self.0.bench_function(&format!("{day}/{step}"), |b| {
+            b.iter(|| func(black_box(input)))
+        });
+    }
+
+    fn skip(&mut self, day: Day, reason: &str) {
+        eprintln!("Skipping day {day}: {reason}.");
+    }
+}
+
+fn main() {
+    let mut criterion = Criterion::default().configure_from_args();
+    register_all(&mut CriterionRegistry(&mut criterion));
+    criterion.final_summary();
+}
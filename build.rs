@@ -0,0 +1,49 @@
+//! Generates the list of solutions for the benchmark harness in `benches/solutions.rs`.
+//! Every `src/bin/DD.rs` is included as a module, its `register` function is generated by `solution!`.
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::{env, fs};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+
+    let mut days: Vec<String> = fs::read_dir(manifest_dir.join("src/bin"))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|x| x.file_name().into_string().ok())
+                .filter_map(|x| x.strip_suffix(".rs").map(String::from))
+                .filter(|x| x.len() == 2 && x.chars().all(|x| x.is_ascii_digit()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort();
+
+    let mut s = String::new();
+
+    for day in &days {
+        let path = manifest_dir.join("src/bin").join(format!("{day}.rs"));
+        // without a test harness, `#[test]` functions are dropped, which leaves test helpers unused.
+        let _ = writeln!(
+            s,
+            "#[path = {:?}]\n#[allow(dead_code, unused_imports)]\nmod day_{day};\n",
+            path.display().to_string()
+        );
+    }
+
+    s.push_str(
+        "/// Passes the steps of all solutions to a benchmark harness.\nfn register_all(registry: &mut impl advent_of_code::template::bench::Registry) {\n",
+    );
+
+    for day in &days {
+        let _ = writeln!(s, "    day_{day}::register(registry);");
+    }
+
+    s.push_str("}\n");
+
+    fs::write(out_dir.join("solutions.rs"), s).unwrap();
+}
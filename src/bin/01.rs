@@ -55,7 +55,7 @@ fn get_digit_from_word(word: &str) -> Option<&str> {
         ("eight", "8"),
         ("nine", "9"),
     ]);
-    return word_to_digit_map.get(word).copied();
+    word_to_digit_map.get(word).copied()
 }
 
 fn extract_digit_and_update_curr_substring(curr_substring: &mut String) -> String {
@@ -104,24 +104,3 @@ pub fn part_two(input: &str) -> Option<u32> {
         .sum();
     Some(all_digits_summed)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(142));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(281));
-    }
-}
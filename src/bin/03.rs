@@ -160,8 +160,10 @@ pub fn part_one(input: &str) -> Option<u32> {
         for node in row {
             if node.value.is_numeric() {
                 curr_number += &node.value.to_string();
-                if !is_curr_number_a_part_number && node.is_adjacent_to_symbol.is_some() {
-                    is_curr_number_a_part_number = node.is_adjacent_to_symbol.unwrap();
+                if !is_curr_number_a_part_number {
+                    if let Some(is_adjacent) = node.is_adjacent_to_symbol {
+                        is_curr_number_a_part_number = is_adjacent;
+                    }
                 }
                 continue;
             }
@@ -222,20 +224,3 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     Some(gear_ratios.iter().sum())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4361));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(467835));
-    }
-}
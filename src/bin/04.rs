@@ -11,10 +11,7 @@ struct Scratchcard {
 fn extract_numbers(numbers: &str) -> Vec<u32> {
     numbers
         .split(' ')
-        .filter_map(|n| match n.trim().parse() {
-            Ok(number) => Some(number),
-            Err(_) => None,
-        })
+        .filter_map(|n| n.trim().parse().ok())
         .collect()
 }
 
@@ -93,16 +90,54 @@ pub fn part_two(input: &str) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::generate::{self, Rng};
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::scratchcards(rng, size.min(20), 5, 8)
+    }
+
+    fn count_matches(card: &str) -> usize {
+        let (_, numbers) = card.split_once(':').unwrap();
+        let (winning, own) = numbers.split_once('|').unwrap();
+        let winning: Vec<&str> = winning.split_whitespace().collect();
+        own.split_whitespace()
+            .filter(|x| winning.contains(x))
+            .count()
+    }
+
+    fn reference_part_one(input: &str) -> Option<u32> {
+        Some(
+            input
+                .lines()
+                .map(|card| match count_matches(card) {
+                    0 => 0,
+                    n => 1 << (n - 1),
+                })
+                .sum(),
+        )
+    }
+
+    /// Processes every single copy, one at a time, like the puzzle describes it.
+    fn reference_part_two(input: &str) -> Option<u32> {
+        let matches: Vec<usize> = input.lines().map(count_matches).collect();
+        let mut pile: Vec<usize> = (0..matches.len()).collect();
+        let mut total = 0;
+
+        while let Some(card) = pile.pop() {
+            total += 1;
+            pile.extend(card + 1..=card + matches[card]);
+        }
+
+        Some(total)
+    }
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(13));
+    fn part_one_matches_reference() {
+        generate::check(generate, part_one, reference_part_one);
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(30));
+    fn part_two_matches_reference() {
+        generate::check(generate, part_two, reference_part_two);
     }
 }
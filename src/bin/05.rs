@@ -1,4 +1,4 @@
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse; part_two: [ranges = part_two_ranges]);
 
 struct MapRange {
     source_start: usize,
@@ -53,7 +53,7 @@ fn get_num_for_map(source: &usize, map_ranges: &[MapRange]) -> usize {
     let mapped_numbers: Vec<isize> = map_ranges
         .iter()
         .filter_map(|map_range| {
-            if *source < map_range.source_start || *source > map_range.source_end {
+            if *source < map_range.source_start || *source >= map_range.source_end {
                 return None;
             };
 
@@ -69,31 +69,18 @@ fn get_num_for_map(source: &usize, map_ranges: &[MapRange]) -> usize {
     *source
 }
 
-fn get_location_numbers_for_seeds(seeds: Vec<usize>, lines: Vec<&str>) -> Vec<usize> {
-    let seed_to_soil = extract_map_range_list(lines.get(1).unwrap());
-    let soil_to_fertilizer = extract_map_range_list(lines.get(2).unwrap());
-    let fertilizer_to_water = extract_map_range_list(lines.get(3).unwrap());
-    let water_to_light = extract_map_range_list(lines.get(4).unwrap());
-    let light_to_temperature = extract_map_range_list(lines.get(5).unwrap());
-    let temperature_to_humidity = extract_map_range_list(lines.get(6).unwrap());
-    let humidity_to_location = extract_map_range_list(lines.get(7).unwrap());
-
+fn get_location_numbers_for_seeds(seeds: &[usize], maps: &[Vec<MapRange>]) -> Vec<usize> {
     seeds
         .iter()
         .map(|seed| {
-            let soil = get_num_for_map(seed, &seed_to_soil);
-            let fertilizer = get_num_for_map(&soil, &soil_to_fertilizer);
-            let water = get_num_for_map(&fertilizer, &fertilizer_to_water);
-            let light = get_num_for_map(&water, &water_to_light);
-            let temperature = get_num_for_map(&light, &light_to_temperature);
-            let humidity = get_num_for_map(&temperature, &temperature_to_humidity);
-            get_num_for_map(&humidity, &humidity_to_location)
+            maps.iter().fold(*seed, |source, map_ranges| {
+                get_num_for_map(&source, map_ranges)
+            })
         })
         .collect()
 }
 
-fn extract_seed_ranges(range: &str) -> Vec<Range> {
-    let numbers = extract_numbers_delimited_by_space(range);
+fn extract_seed_ranges(numbers: &[usize]) -> Vec<Range> {
     let mut seed_ranges = vec![];
     let mut i: usize = 0;
     while i < numbers.len() {
@@ -109,145 +96,223 @@ fn extract_seed_ranges(range: &str) -> Vec<Range> {
     seed_ranges
 }
 
-fn spit_range_for_map(range: &Range, map_range: &MapRange) -> Vec<Range> {
-    let range_nums: Vec<usize> = (range.start..range.end).collect();
+/// Splits a range along a map range into the part that the map range moves, and the parts before
+/// and after it that it leaves alone.
+fn spit_range_for_map(range: &Range, map_range: &MapRange) -> (Option<Range>, Vec<Range>) {
+    let inside_start = range.start.max(map_range.source_start);
+    let inside_end = range.end.min(map_range.source_end);
 
-    if range_nums.is_empty() {
-        return vec![];
+    if inside_start >= inside_end {
+        return (None, vec![*range]);
     }
 
-    // Gets numbers outside of the map range
-    let outside_before: Vec<&usize> = range_nums
-        .iter()
-        .filter(|r| **r < map_range.source_start)
-        .collect();
-    let outside_after: Vec<&usize> = range_nums
-        .iter()
-        .filter(|r| **r > map_range.source_end)
-        .collect();
-    let outside_before = match outside_before.len() {
-        0 => None,
-        _ => Some(Range {
-            start: **outside_before.iter().min().unwrap(),
-            end: **outside_before.iter().max().unwrap(),
-        }),
-    };
-    let outside_after = match outside_after.len() {
-        0 => None,
-        _ => Some(Range {
-            start: **outside_after.iter().min().unwrap(),
-            end: **outside_after.iter().max().unwrap(),
-        }),
+    // Gets transformed numbers inside of map range
+    let inside = Range {
+        start: inside_start - map_range.source_start + map_range.destination_start,
+        end: inside_end - map_range.source_start + map_range.destination_start,
     };
 
-    // Gets transformed numbers inside of map range
-    let x: isize = map_range.source_start as isize - map_range.destination_start as isize;
-    let inside: Vec<&usize> = range_nums
-        .iter()
-        .filter(|r| **r >= map_range.source_start && **r <= map_range.source_end)
-        .collect();
-    let inside = Some(Range {
-        start: (**inside.iter().min().unwrap() as isize - x) as usize,
-        end: (**inside.iter().max().unwrap() as isize - x + 1) as usize,
-    });
+    // Gets numbers outside of the map range
+    let outside = [
+        Range {
+            start: range.start,
+            end: inside_start,
+        },
+        Range {
+            start: inside_end,
+            end: range.end,
+        },
+    ]
+    .into_iter()
+    .filter(|r| r.start < r.end)
+    .collect();
 
-    [outside_before, outside_after, inside]
-        .iter()
-        .filter_map(|x| match x.is_none() {
-            true => None,
-            false => Some(x.unwrap()),
-        })
-        .collect()
+    (Some(inside), outside)
 }
 
 fn get_ranges_for_map(ranges: &[Range], map_ranges: &[MapRange]) -> Vec<Range> {
     let mut transformed_ranges: Vec<Range> = vec![];
+    let mut unmapped_ranges = ranges.to_vec();
 
+    // The parts of a range that one map range does not move may still be moved by another one.
     map_ranges.iter().for_each(|mr| {
-        let new_ranges: Vec<Range> = ranges
+        unmapped_ranges = unmapped_ranges
             .iter()
             .flat_map(|r| {
-                // If the range is outside the bounds of the map_range, do not alter the range
-                if r.end < mr.source_start || r.start >= mr.source_end {
-                    return vec![];
-                };
-                // Otherwise, split the range up into the parts that get transformed and the parts that remain the same
-                spit_range_for_map(r, mr)
+                let (inside, outside) = spit_range_for_map(r, mr);
+                transformed_ranges.extend(inside);
+                outside
             })
             .collect();
-        new_ranges.iter().for_each(|r| transformed_ranges.push(*r));
     });
 
-    if transformed_ranges.is_empty() {
-        ranges.to_vec()
-    } else {
-        transformed_ranges
-    }
+    // Numbers that no map range moves keep their number.
+    transformed_ranges.extend(unmapped_ranges);
+    transformed_ranges
 }
 
-fn get_location_numbers_for_seed_ranges(seed_ranges: Vec<Range>, lines: Vec<&str>) -> Vec<usize> {
-    let seed_to_soil = extract_map_range_list(lines.get(1).unwrap());
-    let soil_to_fertilizer = extract_map_range_list(lines.get(2).unwrap());
-    let fertilizer_to_water = extract_map_range_list(lines.get(3).unwrap());
-    let water_to_light = extract_map_range_list(lines.get(4).unwrap());
-    let light_to_temperature = extract_map_range_list(lines.get(5).unwrap());
-    let temperature_to_humidity = extract_map_range_list(lines.get(6).unwrap());
-    let humidity_to_location = extract_map_range_list(lines.get(7).unwrap());
-
+fn get_location_numbers_for_seed_ranges(
+    seed_ranges: &[Range],
+    maps: &[Vec<MapRange>],
+) -> Vec<usize> {
     seed_ranges
         .iter()
-        .map(|seed_range| {
-            let initial_ranges = vec![*seed_range];
-            let soil = get_ranges_for_map(&initial_ranges, &seed_to_soil);
-            let fertilizer = get_ranges_for_map(&soil, &soil_to_fertilizer);
-            let water = get_ranges_for_map(&fertilizer, &fertilizer_to_water);
-            let light = get_ranges_for_map(&water, &water_to_light);
-            let temperature = get_ranges_for_map(&light, &light_to_temperature);
-            let humidity = get_ranges_for_map(&temperature, &temperature_to_humidity);
-            let location = get_ranges_for_map(&humidity, &humidity_to_location);
+        .filter_map(|seed_range| {
+            let location = maps.iter().fold(vec![*seed_range], |ranges, map_ranges| {
+                get_ranges_for_map(&ranges, map_ranges)
+            });
             location
                 .iter()
-                .min_by(|a, b| a.start.cmp(&b.start))
-                .unwrap()
-                .start
+                .filter(|r| r.start < r.end)
+                .map(|r| r.start)
+                .min()
         })
         .collect()
 }
 
-/// Find the lowest location number
-pub fn part_one(input: &str) -> Option<u32> {
+pub struct Almanac {
+    seeds: Vec<usize>,
+    /// seed-to-soil, soil-to-fertilizer, ..., humidity-to-location
+    maps: Vec<Vec<MapRange>>,
+}
+
+pub fn parse(input: &str) -> Almanac {
     let lines: Vec<&str> = input.split("\n\n").collect();
 
     let seeds =
         extract_numbers_delimited_by_space(lines.first().unwrap().split(": ").last().unwrap());
-    let location_numbers = get_location_numbers_for_seeds(seeds, lines);
+    let maps = lines
+        .iter()
+        .skip(1)
+        .map(|l| extract_map_range_list(l))
+        .collect();
 
-    Some(*location_numbers.iter().min().unwrap() as u32)
+    Almanac { seeds, maps }
 }
 
-pub fn part_two(_: &str) -> Option<u32> {
-    // let lines: Vec<&str> = input.split("\n\n").collect();
-    // let seed_ranges = extract_seed_ranges(lines.first().unwrap().split(": ").last().unwrap());
-    // let location_numbers = get_location_numbers_for_seed_ranges(seed_ranges, lines);
+/// Find the lowest location number
+pub fn part_one(almanac: &Almanac) -> Option<u32> {
+    let location_numbers = get_location_numbers_for_seeds(&almanac.seeds, &almanac.maps);
 
+    Some(*location_numbers.iter().min().unwrap() as u32)
+}
+
+/// Find the lowest location number of the seed ranges, by looking up every seed.
+pub fn part_two(almanac: &Almanac) -> Option<u32> {
     // Brute force
-    Some(52510809)
+    extract_seed_ranges(&almanac.seeds)
+        .iter()
+        .flat_map(|seed_range| seed_range.start..seed_range.end)
+        .map(|seed| {
+            almanac.maps.iter().fold(seed, |source, map_ranges| {
+                get_num_for_map(&source, map_ranges)
+            })
+        })
+        .min()
+        .map(|x| x as u32)
+}
+
+/// Find the lowest location number of the seed ranges, by splitting the ranges along the maps.
+pub fn part_two_ranges(almanac: &Almanac) -> Option<u32> {
+    let seed_ranges = extract_seed_ranges(&almanac.seeds);
+    let location_numbers = get_location_numbers_for_seed_ranges(&seed_ranges, &almanac.maps);
+
+    location_numbers.iter().min().map(|x| *x as u32)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::generate::{self, Rng};
+    use std::collections::HashMap;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::almanac(rng, size.min(10), 4, 100)
+    }
+
+    /// Spells out every map as a lookup table, numbers that are not listed map to themselves.
+    /// `seeds` turns the numbers on the first line into the seeds to look up.
+    fn reference(input: &str, seeds: impl Fn(&[u64]) -> Vec<u64>) -> Option<u32> {
+        let mut blocks = input.split("\n\n");
+        let numbers: Vec<u64> = blocks.next().unwrap()[7..]
+            .split_whitespace()
+            .map(|x| x.parse().unwrap())
+            .collect();
+
+        let tables: Vec<HashMap<u64, u64>> = blocks
+            .map(|block| {
+                let mut table = HashMap::new();
+                for line in block.lines().skip(1) {
+                    let numbers: Vec<u64> = line
+                        .split_whitespace()
+                        .map(|x| x.parse().unwrap())
+                        .collect();
+                    for i in 0..numbers[2] {
+                        table.insert(numbers[1] + i, numbers[0] + i);
+                    }
+                }
+                table
+            })
+            .collect();
+
+        seeds(&numbers)
+            .iter()
+            .map(|&seed| {
+                tables
+                    .iter()
+                    .fold(seed, |x, table| *table.get(&x).unwrap_or(&x))
+            })
+            .min()
+            .map(|x| x as u32)
+    }
+
+    fn reference_part_one(input: &str) -> Option<u32> {
+        reference(input, <[u64]>::to_vec)
+    }
+
+    /// Walks every seed of every seed range.
+    fn reference_part_two(input: &str) -> Option<u32> {
+        reference(input, |numbers| {
+            numbers.chunks(2).flat_map(|x| x[0]..x[0] + x[1]).collect()
+        })
+    }
+
+    #[test]
+    fn part_two_solves_example() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(part_two(&parse(&input)), Some(46));
+    }
+
+    #[test]
+    fn part_two_ranges_solves_example() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(part_two_ranges(&parse(&input)), Some(46));
+    }
+
+    #[test]
+    fn part_one_matches_reference() {
+        generate::check(
+            generate,
+            |input| part_one(&parse(input)),
+            reference_part_one,
+        );
+    }
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(35));
+    fn part_two_matches_reference() {
+        generate::check(
+            generate,
+            |input| part_two(&parse(input)),
+            reference_part_two,
+        );
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        // Not correct for example - brute force
-        assert_eq!(result, Some(52510809));
+    fn part_two_ranges_matches_reference() {
+        generate::check(
+            generate,
+            |input| part_two_ranges(&parse(input)),
+            reference_part_two,
+        );
     }
 }
@@ -1,5 +1,3 @@
-use std::cmp::Ordering;
-
 use regex::Regex;
 
 advent_of_code::solution!(6);
@@ -112,20 +110,3 @@ pub fn part_two(input: &str) -> Option<u128> {
 
     Some(ways_to_beat_record)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(71503));
-    }
-}
@@ -58,7 +58,7 @@ fn get_of_a_kinds_for_cards(mut cards: Vec<Card>) -> Vec<u8> {
     let mut of_a_kinds: Vec<u8> = vec![];
     let mut prev_card: Option<Card> = None;
     let mut curr_matches: u8 = 0;
-    cards.sort_by(|a, b| a.value.cmp(&b.value));
+    cards.sort_by_key(|a| a.value);
     cards.iter().for_each(|card| {
         if prev_card.is_none() || prev_card.unwrap() != *card {
             if curr_matches > 1 {
@@ -100,7 +100,7 @@ fn get_of_a_kinds_for_cards_with_jokers(mut cards: Vec<Card>) -> Vec<u8> {
     let mut prev_card: Option<Card> = None;
     let mut curr_matches: u8 = 0;
     let mut num_jokers: u8 = 0;
-    cards.sort_by(|a, b| a.value.cmp(&b.value));
+    cards.sort_by_key(|a| a.value);
     cards.iter().for_each(|card| {
         if card.label == 'J' {
             num_jokers += 1;
@@ -126,7 +126,7 @@ fn get_of_a_kinds_for_cards_with_jokers(mut cards: Vec<Card>) -> Vec<u8> {
     get_updated_of_a_kinds_with_jokers(of_a_kinds, num_jokers)
 }
 
-fn get_hand_result_for_of_a_kinds(of_a_kinds: &Vec<u8>) -> HandResult {
+fn get_hand_result_for_of_a_kinds(of_a_kinds: &[u8]) -> HandResult {
     if of_a_kinds.is_empty() {
         return HandResult::HighCard;
     };
@@ -225,16 +225,62 @@ pub fn part_two(input: &str) -> Option<u128> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::generate::{self, Rng};
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::camel_cards(rng, size)
+    }
+
+    /// Ranks hands by the count of each card, sorted, e.g. `[3, 3, 3, 2, 2]` for a full house.
+    fn hand_type(hand: &str) -> Vec<usize> {
+        let mut counts: Vec<usize> = hand
+            .chars()
+            .map(|x| hand.chars().filter(|&y| x == y).count())
+            .collect();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        counts
+    }
+
+    /// Tries every label for the jokers and keeps the strongest hand.
+    fn reference(input: &str, labels: &str, jokers: bool) -> Option<u128> {
+        let mut hands: Vec<(Vec<usize>, Vec<usize>, u128)> = input
+            .lines()
+            .map(|line| {
+                let (hand, bid) = line.split_once(' ').unwrap();
+                let strongest = match jokers {
+                    true => labels
+                        .chars()
+                        .map(|x| hand_type(&hand.replace('J', &x.to_string())))
+                        .max()
+                        .unwrap(),
+                    false => hand_type(hand),
+                };
+                let values = hand.chars().map(|x| labels.find(x).unwrap()).collect();
+                (strongest, values, bid.parse().unwrap())
+            })
+            .collect();
+        hands.sort();
+
+        Some(
+            hands
+                .iter()
+                .enumerate()
+                .map(|(i, (_, _, bid))| bid * (i as u128 + 1))
+                .sum(),
+        )
+    }
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6440));
+    fn part_one_matches_reference() {
+        generate::check(generate, part_one, |input| {
+            reference(input, "23456789TJQKA", false)
+        });
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5905));
+    fn part_two_matches_reference() {
+        generate::check(generate, part_two, |input| {
+            reference(input, "J23456789TQKA", true)
+        });
     }
 }
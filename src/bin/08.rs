@@ -28,9 +28,7 @@ fn get_coordinates(input: &str) -> HashMap<String, (String, String)> {
         .lines()
         .rev()
         .filter_map(|l| {
-            let Some((curr, next)) = l.split_once('=') else {
-                return None;
-            };
+            let (curr, next) = l.split_once('=')?;
             let binding = next.trim().replace(['(', ')'], "");
             let next = binding.split_once(',').unwrap();
             let (next_left, next_right) = next;
@@ -110,28 +108,3 @@ pub fn part_two(input: &str) -> Option<u128> {
 
     steps_to_reach_z.into_iter().reduce(lcm)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(2));
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(6));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
-        ));
-        assert_eq!(result, Some(6));
-    }
-}
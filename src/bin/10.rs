@@ -26,11 +26,16 @@ fn is_connected(from_dir: Direction, pipe: char) -> bool {
     }
 }
 
-fn add_within_bounds(from: (usize, usize), dir: Direction, n: usize) -> Option<(usize, usize)> {
+fn add_within_bounds(
+    from: (usize, usize),
+    dir: Direction,
+    rows: usize,
+    cols: usize,
+) -> Option<(usize, usize)> {
     let to_r = from.0 as i32 + dir.0 as i32;
     let to_c = from.1 as i32 + dir.1 as i32;
 
-    if to_r < 0 || to_r >= n as i32 || to_c < 0 || to_c >= n as i32 {
+    if to_r < 0 || to_r >= rows as i32 || to_c < 0 || to_c >= cols as i32 {
         None
     } else {
         Some((to_r as usize, to_c as usize))
@@ -55,7 +60,8 @@ pub fn part_one(input: &str) -> Option<u32> {
                 .collect()
         })
         .collect();
-    let n = graph.len();
+    let rows = graph.len();
+    let cols = graph[0].len();
 
     let mut distances_from_start = HashMap::new();
     distances_from_start.insert(start, 0u32);
@@ -63,10 +69,14 @@ pub fn part_one(input: &str) -> Option<u32> {
 
     while let Some(curr) = to_visit.pop_front() {
         DIRECTIONS.iter().for_each(|dir| {
-            let Some(next) = add_within_bounds(curr, *dir, n) else {
+            let Some(next) = add_within_bounds(curr, *dir, rows, cols) else {
                 return;
             };
-            if distances_from_start.get(&next).is_some()
+            // the current pipe has to lead there as well, except for the start, whose pipe is unknown.
+            let leads_to_next = graph[curr.0][curr.1] == START
+                || is_connected((-dir.0, -dir.1), graph[curr.0][curr.1]);
+            if distances_from_start.contains_key(&next)
+                || !leads_to_next
                 || !is_connected(*dir, graph[next.0][next.1])
             {
                 return;
@@ -80,31 +90,66 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(*distances_from_start.values().max().unwrap())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(_input: &str) -> Option<u32> {
     None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::generate::{self, Rng};
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(4));
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(8));
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let rows = 2 + rng.below(size as u64) as usize;
+        let cols = 2 + rng.below(size as u64) as usize;
+        generate::pipe_loop(rng, rows, cols)
+    }
+
+    /// Walks once around the loop, the farthest tile is halfway.
+    fn reference_part_one(input: &str) -> Option<u32> {
+        let grid: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+        let tile = |(r, c): (i32, i32)| {
+            let row = grid.get(usize::try_from(r).ok()?)?;
+            row.get(usize::try_from(c).ok()?).copied()
+        };
+        let exits = |pipe: u8| -> &[(i32, i32)] {
+            match pipe {
+                b'|' => &[(-1, 0), (1, 0)],
+                b'-' => &[(0, -1), (0, 1)],
+                b'L' => &[(-1, 0), (0, 1)],
+                b'J' => &[(-1, 0), (0, -1)],
+                b'7' => &[(1, 0), (0, -1)],
+                b'F' => &[(1, 0), (0, 1)],
+                _ => &[],
+            }
+        };
+
+        let start = (0..grid.len() as i32)
+            .flat_map(|r| (0..grid[0].len() as i32).map(move |c| (r, c)))
+            .find(|&x| tile(x) == Some(b'S'))?;
+
+        // leave the start towards a neighbour that leads back into it.
+        let mut dir = [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .find(|&(dr, dc)| {
+                tile((start.0 + dr, start.1 + dc)).is_some_and(|x| exits(x).contains(&(-dr, -dc)))
+            })?;
+        let mut position = (start.0 + dir.0, start.1 + dir.1);
+        let mut length = 1;
+
+        while position != start {
+            dir = *exits(tile(position)?)
+                .iter()
+                .find(|&&x| x != (-dir.0, -dir.1))?;
+            position = (position.0 + dir.0, position.1 + dir.1);
+            length += 1;
+        }
+
+        Some(length / 2)
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, None);
+    fn part_one_matches_reference() {
+        generate::check(generate, part_one, reference_part_one);
     }
 }
@@ -0,0 +1,116 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A source for the current time.
+/// Time-dependent functions take a clock so that they can be tested with a fixed time.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+/// The system's wall clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that always returns the same time.
+pub struct FixedClock(pub SystemTime);
+
+impl FixedClock {
+    /// Creates a clock fixed at the given number of seconds after the unix epoch.
+    #[must_use]
+    pub fn from_unix_secs(secs: u64) -> Self {
+        Self(UNIX_EPOCH + Duration::from_secs(secs))
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Puzzles unlock at midnight EST, i.e. UTC-5.
+pub(crate) const PUZZLE_UTC_OFFSET_SECS: i64 = -5 * 60 * 60;
+
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Days since the unix epoch for a date of the proleptic gregorian calendar.
+/// See: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Date of the proleptic gregorian calendar for a number of days since the unix epoch.
+/// See: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Seconds since the unix epoch, negative for times before it.
+#[allow(clippy::cast_possible_wrap)]
+pub(crate) fn unix_secs(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(x) => x.as_secs() as i64,
+        Err(e) => -(e.duration().as_secs() as i64),
+    }
+}
+
+/// The date (year, month, day) in the timezone puzzles are released in.
+pub(crate) fn puzzle_date(time: SystemTime) -> (i64, u32, u32) {
+    let local_secs = unix_secs(time) + PUZZLE_UTC_OFFSET_SECS;
+    civil_from_days(local_secs.div_euclid(SECS_PER_DAY))
+}
+
+/// The instant a date starts in the timezone puzzles are released in.
+#[allow(clippy::cast_sign_loss)]
+pub(crate) fn puzzle_midnight(year: i64, month: u32, day: u32) -> SystemTime {
+    let secs = days_from_civil(year, month, day) * SECS_PER_DAY - PUZZLE_UTC_OFFSET_SECS;
+    UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{civil_from_days, days_from_civil, puzzle_date, Clock, FixedClock};
+
+    #[test]
+    fn converts_dates() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2023, 12, 1), 19692);
+        assert_eq!(civil_from_days(19692), (2023, 12, 1));
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+    }
+
+    #[test]
+    fn puzzle_date_is_in_est() {
+        // 2023-12-01T04:59:59Z is still Nov 30 in EST.
+        let clock = FixedClock::from_unix_secs(1_701_406_799);
+        assert_eq!(puzzle_date(clock.now()), (2023, 11, 30));
+        let clock = FixedClock::from_unix_secs(1_701_406_800);
+        assert_eq!(puzzle_date(clock.now()), (2023, 12, 1));
+    }
+}
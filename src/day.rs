@@ -1,6 +1,10 @@
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use crate::clock::{puzzle_date, puzzle_midnight, Clock, SystemClock};
+use crate::Year;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
@@ -35,6 +39,36 @@ impl Day {
     pub fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// The day of advent it currently is in the puzzle timezone (UTC-5),
+    /// returns [`None`] outside of advent.
+    pub fn today() -> Option<Self> {
+        Self::today_with(&SystemClock)
+    }
+
+    /// Same as [`Day::today`], with the current time provided by `clock`.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn today_with(clock: &impl Clock) -> Option<Self> {
+        let (_, month, day) = puzzle_date(clock.now());
+        if month != 12 {
+            return None;
+        }
+        Self::new(day as u8)
+    }
+
+    /// The instant the puzzle of this day unlocks in `year`, i.e. midnight UTC-5 on December N.
+    pub fn unlock_time(self, year: Year) -> SystemTime {
+        puzzle_midnight(i64::from(year.into_inner()), 12, u32::from(self.0))
+    }
+
+    /// The time left until the puzzle of this day unlocks in `year`,
+    /// returns [`None`] if it is already unlocked.
+    pub fn time_until_unlock(self, year: Year, clock: &impl Clock) -> Option<Duration> {
+        self.unlock_time(year)
+            .duration_since(clock.now())
+            .ok()
+            .filter(|x| !x.is_zero())
+    }
 }
 
 impl Display for Day {
@@ -61,6 +95,9 @@ impl FromStr for Day {
     type Err = DayFromStrError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "today" {
+            return Self::today().ok_or(DayFromStrError);
+        }
         let day = s.parse().map_err(|_| DayFromStrError)?;
         Self::new(day).ok_or(DayFromStrError)
     }
@@ -74,7 +111,7 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        f.write_str("expecting a day number between 1 and 25, or `today` during advent")
     }
 }
 
@@ -135,6 +172,53 @@ macro_rules! day {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{all_days, Day};
+    use crate::{FixedClock, Year};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn today() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(
+            Day::today_with(&FixedClock::from_unix_secs(1_701_406_800)),
+            Some(Day(1))
+        );
+        // 2023-12-01T04:59:59Z
+        assert_eq!(
+            Day::today_with(&FixedClock::from_unix_secs(1_701_406_799)),
+            None
+        );
+        // 2023-12-26T05:00:00Z
+        assert_eq!(
+            Day::today_with(&FixedClock::from_unix_secs(1_703_566_800)),
+            None
+        );
+    }
+
+    #[test]
+    fn unlock_time() {
+        let year = Year::new(2023).unwrap();
+        assert_eq!(
+            Day(1).unlock_time(year),
+            UNIX_EPOCH + Duration::from_secs(1_701_406_800)
+        );
+        assert_eq!(
+            Day(25).unlock_time(year),
+            UNIX_EPOCH + Duration::from_secs(1_703_480_400)
+        );
+    }
+
+    #[test]
+    fn time_until_unlock() {
+        let year = Year::new(2023).unwrap();
+        assert_eq!(
+            Day(1).time_until_unlock(year, &FixedClock::from_unix_secs(1_701_406_790)),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            Day(1).time_until_unlock(year, &FixedClock::from_unix_secs(1_701_406_800)),
+            None
+        );
+    }
 
     #[test]
     fn all_days_iterator() {
@@ -1,4 +1,10 @@
+mod clock;
 mod day;
+mod part;
 pub mod template;
+mod year;
 
+pub use clock::{Clock, FixedClock, SystemClock};
 pub use day::*;
+pub use part::*;
+pub use year::*;
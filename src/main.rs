@@ -1,69 +1,256 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, bench_compare, completions, config, crosscheck, decrypt_inputs, download, encrypt_inputs,
+    examples, extract_examples, inspect, read, readme, scaffold, solve, test_day,
+};
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
+    use std::path::PathBuf;
+    use std::{env, process};
 
-    use advent_of_code::Day;
+    use advent_of_code::template::cli::{self, Shell};
+    use advent_of_code::template::commands::config::Action;
+    use advent_of_code::template::config::config;
+    use advent_of_code::{Day, Part};
 
     pub enum AppArguments {
+        BenchCompare {
+            rev: String,
+            days: Option<Vec<Day>>,
+            stable: bool,
+            threshold: Option<f64>,
+        },
+        Completions {
+            shell: Shell,
+        },
+        Config {
+            action: Action,
+        },
+        Crosscheck {
+            day: Day,
+            release: bool,
+        },
+        DecryptInputs {
+            force: bool,
+        },
         Download {
             day: Day,
+            wait: bool,
+        },
+        EncryptInputs {
+            keep: bool,
+            new_key: bool,
+        },
+        Examples {
+            day: Option<Day>,
+        },
+        ExtractExamples {
+            day: Day,
+            select: Option<Vec<usize>>,
+            force: bool,
+        },
+        Inspect {
+            day: Day,
         },
         Read {
             day: Day,
+            refresh: bool,
+        },
+        Readme {
+            leaderboard: Option<PathBuf>,
+            member: Option<String>,
         },
         Scaffold {
             day: Day,
+            template: Option<String>,
+            types: Option<Vec<String>>,
+            force: bool,
+            examples: Option<usize>,
+            download: bool,
         },
         Solve {
             day: Day,
             release: bool,
             time: bool,
-            submit: Option<u8>,
+            stable: bool,
+            allocs: bool,
+            profile: bool,
+            variant: Option<String>,
+            all_inputs: bool,
+            timeout: Option<u64>,
+            submit: Option<Part>,
+            watch: bool,
+        },
+        TestDay {
+            day: Day,
+            release: bool,
+            watch: bool,
         },
         All {
             release: bool,
             time: bool,
+            stable: bool,
+            allocs: bool,
+            timeout: Option<u64>,
+            export: Option<PathBuf>,
         },
     }
 
+    /// Whether a flag is set, falling back to its default in the project config unless it is turned off.
+    fn flag(args: &mut pico_args::Arguments, on: &cli::Arg, off: &cli::Arg, default: bool) -> bool {
+        let (on, off) = (args.contains(on.name), args.contains(off.name));
+        on || (default && !off)
+    }
+
+    /// Parses a comma-separated list of days, e.g. `1,6`.
+    fn parse_days(s: &str) -> Result<Vec<Day>, advent_of_code::DayFromStrError> {
+        s.split(',').map(|x| x.trim().parse()).collect()
+    }
+
+    /// Parses a comma-separated list of numbers, e.g. `1,3`.
+    fn parse_list(s: &str) -> Result<Vec<usize>, std::num::ParseIntError> {
+        s.split(',').map(|x| x.trim().parse()).collect()
+    }
+
+    fn parse_types(s: &str) -> Result<Vec<String>, &'static str> {
+        let types: Vec<String> = s.split(',').map(|x| x.trim().to_string()).collect();
+        match types.len() {
+            1 | 2 if types.iter().all(|x| !x.is_empty()) => Ok(types),
+            _ => Err("expecting one return type, or two separated by a comma"),
+        }
+    }
+
+    /// Prints help or exits if the arguments do not match the command definitions in [`cli`].
+    fn validate(raw: &[String]) -> Result<(), cli::Error> {
+        let Some(name) = raw.first().filter(|x| !x.starts_with('-')) else {
+            print!("{}", cli::format_overview());
+            process::exit(if cli::is_help(raw) { 0 } else { 1 });
+        };
+
+        let Some(command) = cli::find_command(name) else {
+            eprintln!("Unknown command: {name}");
+            eprint!("{}", cli::format_overview());
+            process::exit(1);
+        };
+
+        if cli::is_help(&raw[1..]) {
+            print!("{}", cli::format_help(command, "cargo"));
+            process::exit(0);
+        }
+
+        cli::validate(command, &raw[1..])
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        let raw: Vec<String> = env::args().skip(1).collect();
+        validate(&raw)?;
+
+        let mut args = pico_args::Arguments::from_vec(raw.into_iter().map(Into::into).collect());
+        let defaults = &config().defaults;
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+                release: flag(&mut args, &cli::RELEASE, &cli::NO_RELEASE, defaults.release),
+                time: flag(&mut args, &cli::TIME, &cli::NO_TIME, defaults.time),
+                stable: args.contains(cli::STABLE.name),
+                allocs: flag(&mut args, &cli::ALLOCS, &cli::NO_ALLOCS, defaults.allocs),
+                timeout: args
+                    .opt_value_from_fn(cli::TIMEOUT.name, cli::parse_timeout)?
+                    .or(defaults.timeout),
+                export: args.opt_value_from_str(cli::EXPORT.name)?,
+            },
+            Some("bench-compare") => AppArguments::BenchCompare {
+                rev: args.free_from_str()?,
+                days: args.opt_value_from_fn(cli::DAYS.name, parse_days)?,
+                stable: args.contains(cli::STABLE.name),
+                threshold: args.opt_value_from_str(cli::THRESHOLD.name)?,
+            },
+            Some("completions") => AppArguments::Completions {
+                shell: args.free_from_str()?,
+            },
+            Some("aoc-config") => AppArguments::Config {
+                action: match args.free_from_str::<String>()?.as_str() {
+                    "show" => Action::Show,
+                    "set" => match (args.opt_free_from_str()?, args.opt_free_from_str()?) {
+                        (Some(key), Some(value)) => Action::Set { key, value },
+                        _ => return Err("`aoc-config set` expects a key and a value".into()),
+                    },
+                    x => {
+                        return Err(
+                            format!("unknown action `{x}`, expecting `show` or `set`").into()
+                        )
+                    }
+                },
+            },
+            Some("crosscheck") => AppArguments::Crosscheck {
+                day: args.free_from_str()?,
+                release: flag(&mut args, &cli::RELEASE, &cli::NO_RELEASE, defaults.release),
+            },
+            Some("decrypt-inputs") => AppArguments::DecryptInputs {
+                force: args.contains(cli::DECRYPT_FORCE.name),
             },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                wait: args.contains(cli::WAIT.name),
+            },
+            Some("encrypt-inputs") => AppArguments::EncryptInputs {
+                keep: args.contains(cli::KEEP.name),
+                new_key: args.contains(cli::NEW_KEY.name),
+            },
+            Some("examples") => AppArguments::Examples {
+                day: args.opt_free_from_str()?,
+            },
+            Some("extract-examples") => AppArguments::ExtractExamples {
+                day: args.free_from_str()?,
+                select: args.opt_value_from_fn(cli::SELECT.name, parse_list)?,
+                force: args.contains(cli::EXTRACT_FORCE.name),
+            },
+            Some("inspect") => AppArguments::Inspect {
+                day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
+                refresh: args.contains(cli::REFRESH.name),
+            },
+            Some("readme") => AppArguments::Readme {
+                leaderboard: args.opt_value_from_str(cli::LEADERBOARD.name)?,
+                member: args.opt_value_from_str(cli::MEMBER.name)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
+                template: args.opt_value_from_str(cli::TEMPLATE.name)?,
+                types: args.opt_value_from_fn(cli::TYPE.name, parse_types)?,
+                force: args.contains(cli::SCAFFOLD_FORCE.name),
+                examples: args.opt_value_from_str(cli::SCAFFOLD_EXAMPLES.name)?,
+                download: args.contains(cli::DOWNLOAD.name),
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                release: flag(&mut args, &cli::RELEASE, &cli::NO_RELEASE, defaults.release),
+                submit: args.opt_value_from_str(cli::SUBMIT.name)?,
+                time: flag(&mut args, &cli::TIME, &cli::NO_TIME, defaults.time),
+                stable: args.contains(cli::STABLE.name),
+                allocs: flag(&mut args, &cli::ALLOCS, &cli::NO_ALLOCS, defaults.allocs),
+                profile: args.contains(cli::PROFILE.name),
+                variant: args.opt_value_from_str(cli::VARIANT.name)?,
+                all_inputs: args.contains(cli::ALL_INPUTS.name),
+                timeout: args
+                    .opt_value_from_fn(cli::TIMEOUT.name, cli::parse_timeout)?
+                    .or(defaults.timeout),
+                watch: args.contains(cli::WATCH.name),
             },
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            Some("test-day") => AppArguments::TestDay {
+                day: args.free_from_str()?,
+                release: flag(&mut args, &cli::RELEASE, &cli::NO_RELEASE, defaults.release),
+                watch: args.contains(cli::WATCH.name),
+            },
+            // unknown and missing commands are handled by `validate`.
+            _ => unreachable!(),
         };
 
         let remaining = args.finish();
         if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+            return Err(format!("unexpected argument(s): {remaining:?}").into());
         }
 
         Ok(app_args)
@@ -77,16 +264,92 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::All {
+                release,
+                time,
+                stable,
+                allocs,
+                timeout,
+                export,
+            } => all::handle(
+                release,
+                time || stable,
+                stable,
+                allocs,
+                timeout,
+                export.as_deref(),
+            ),
+            AppArguments::BenchCompare {
+                rev,
+                days,
+                stable,
+                threshold,
+            } => bench_compare::handle(&rev, days, stable, threshold),
+            AppArguments::Completions { shell } => completions::handle(shell),
+            AppArguments::Config { action } => config::handle(action),
+            AppArguments::Crosscheck { day, release } => crosscheck::handle(day, release),
+            AppArguments::DecryptInputs { force } => decrypt_inputs::handle(force),
+            AppArguments::Download { day, wait } => download::handle(day, wait),
+            AppArguments::EncryptInputs { keep, new_key } => encrypt_inputs::handle(keep, new_key),
+            AppArguments::Examples { day } => examples::handle(day),
+            AppArguments::ExtractExamples { day, select, force } => {
+                extract_examples::handle(day, select, force);
+            }
+            AppArguments::Inspect { day } => inspect::handle(day),
+            AppArguments::Read { day, refresh } => read::handle(day, refresh),
+            AppArguments::Readme {
+                leaderboard,
+                member,
+            } => readme::handle(leaderboard.as_deref(), member.as_deref()),
+            AppArguments::Scaffold {
+                day,
+                template,
+                types,
+                force,
+                examples,
+                download,
+            } => scaffold::handle(
+                day,
+                &scaffold::Options {
+                    template: template.as_deref(),
+                    types: types.as_deref(),
+                    force,
+                    examples,
+                    download,
+                },
+            ),
             AppArguments::Solve {
                 day,
                 release,
                 time,
+                stable,
+                allocs,
+                profile,
+                variant,
+                all_inputs,
+                timeout,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                watch,
+            } => solve::handle(
+                day,
+                &solve::Options {
+                    release,
+                    time,
+                    stable,
+                    allocs,
+                    profile,
+                    variant,
+                    all_inputs,
+                    timeout,
+                    submit,
+                },
+                watch,
+            ),
+            AppArguments::TestDay {
+                day,
+                release,
+                watch,
+            } => test_day::handle(day, release, watch),
         },
     };
 }
@@ -0,0 +1,117 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{Day, Year};
+
+/// One of the two parts of a puzzle.
+///
+/// # Display
+/// This value displays as its number.
+///
+/// ```
+/// # use advent_of_code::Part;
+/// let part = Part::new(2).unwrap();
+/// assert_eq!(part.to_string(), "2")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Creates a [`Part`] from the provided number if it is 1 or 2,
+    /// returns [`None`] otherwise.
+    pub fn new(part: u8) -> Option<Self> {
+        match part {
+            1 => Some(Self::One),
+            2 => Some(Self::Two),
+            _ => None,
+        }
+    }
+
+    /// Converts the [`Part`] into its number.
+    pub fn into_inner(self) -> u8 {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.into_inner())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Part {
+    type Err = PartFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let part = s.parse().map_err(|_| PartFromStrError)?;
+        Self::new(part).ok_or(PartFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Part`].
+#[derive(Debug)]
+pub struct PartFromStrError;
+
+impl Error for PartFromStrError {}
+
+impl Display for PartFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a part number, 1 or 2")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies one part of a puzzle, e.g. to submit an answer for it.
+///
+/// # Display
+/// ```
+/// # use advent_of_code::{day, Part, PuzzleKey, Year};
+/// let key = PuzzleKey { year: Year::new(2023).unwrap(), day: day!(8), part: Part::Two };
+/// assert_eq!(key.to_string(), "2023 day 08 part 2")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleKey {
+    pub year: Year,
+    pub day: Day,
+    pub part: Part,
+}
+
+impl Display for PuzzleKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {} part {}", self.year, self.day, self.part)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Part;
+
+    #[test]
+    fn parses_parts() {
+        assert_eq!("1".parse::<Part>().unwrap(), Part::One);
+        assert_eq!("2".parse::<Part>().unwrap(), Part::Two);
+        assert!("0".parse::<Part>().is_err());
+        assert!("3".parse::<Part>().is_err());
+        assert!("one".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn round_trips_numbers() {
+        for part in [Part::One, Part::Two] {
+            assert_eq!(Part::new(part.into_inner()), Some(part));
+            assert_eq!(part.to_string().parse::<Part>().unwrap(), part);
+        }
+    }
+}
@@ -0,0 +1,207 @@
+/// A global allocator that counts allocations, used to report memory usage of solutions.
+/// Enable it with the `alloc_tracking` feature.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+#[cfg(feature = "alloc_tracking")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+// counters are per thread, so that threads running next to a measurement, e.g. the worker of a part
+// that timed out, do not count towards it. `const` initializers keep them free of allocations.
+thread_local! {
+    static MEASURING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static ALLOCATED_BYTES: Cell<u64> = const { Cell::new(0) };
+    /// Bytes allocated minus bytes freed since the start of the measurement, negative if it freed
+    /// memory that was allocated before.
+    static LIVE_BYTES: Cell<i64> = const { Cell::new(0) };
+    static PEAK_LIVE_BYTES: Cell<i64> = const { Cell::new(0) };
+}
+
+/// Wraps the [`System`] allocator and keeps track of allocation count, allocated bytes and peak live bytes.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // a reallocation counts as a new allocation of `new_size` replacing the old one.
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn is_measuring() -> bool {
+    // `try_with` fails while the thread is torn down, its allocations are not measured.
+    MEASURING.try_with(Cell::get).unwrap_or(false)
+}
+
+fn record_alloc(size: usize) {
+    if !is_measuring() {
+        return;
+    }
+
+    ALLOCATIONS.set(ALLOCATIONS.get() + 1);
+    ALLOCATED_BYTES.set(ALLOCATED_BYTES.get() + size as u64);
+    let live = LIVE_BYTES.get() + size as i64;
+    LIVE_BYTES.set(live);
+    PEAK_LIVE_BYTES.set(PEAK_LIVE_BYTES.get().max(live));
+}
+
+fn record_dealloc(size: usize) {
+    if is_measuring() {
+        LIVE_BYTES.set(LIVE_BYTES.get() - size as i64);
+    }
+}
+
+/// Allocations performed while running a closure.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations (including reallocations).
+    pub allocations: u64,
+    /// Total bytes requested over all allocations.
+    pub bytes: u64,
+    /// Highest number of bytes that were live at the same time, relative to the start of the run.
+    pub peak_bytes: u64,
+}
+
+/// Returns `true` if the counting allocator is registered as global allocator.
+#[must_use]
+pub const fn is_enabled() -> bool {
+    cfg!(feature = "alloc_tracking")
+}
+
+/// Run `func` and record its allocations.
+/// Only allocations on the current thread count, threads spawned by `func` are not measured.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, AllocStats) {
+    ALLOCATIONS.set(0);
+    ALLOCATED_BYTES.set(0);
+    LIVE_BYTES.set(0);
+    PEAK_LIVE_BYTES.set(0);
+    MEASURING.set(true);
+
+    let result = func();
+
+    MEASURING.set(false);
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.get(),
+        bytes: ALLOCATED_BYTES.get(),
+        peak_bytes: PEAK_LIVE_BYTES.get().unsigned_abs(),
+    };
+
+    (result, stats)
+}
+
+/// Formats a byte count with binary units, e.g. `1.5KiB`.
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes}B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+
+    for next_unit in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next_unit;
+    }
+
+    format!("{value:.1}{unit}")
+}
+
+impl std::fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} total, {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, AllocStats};
+
+    #[cfg(feature = "alloc_tracking")]
+    #[test]
+    fn ignores_other_threads() {
+        use std::sync::mpsc;
+        use std::thread;
+
+        let (tx, rx) = mpsc::channel::<()>();
+        let worker = thread::spawn(move || {
+            let mut buffers = vec![];
+            while rx.try_recv().is_err() {
+                buffers.push(vec![0u8; 1024]);
+                thread::yield_now();
+            }
+        });
+
+        let (_, stats) = super::measure(|| {
+            let buffer = vec![0u8; 100];
+            thread::sleep(std::time::Duration::from_millis(20));
+            buffer.len()
+        });
+
+        tx.send(()).unwrap();
+        worker.join().unwrap();
+
+        assert_eq!(stats.allocations, 1);
+        assert_eq!(stats.bytes, 100);
+        assert_eq!(stats.peak_bytes, 100);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0B");
+        assert_eq!(format_bytes(1023), "1023B");
+        assert_eq!(format_bytes(1024), "1.0KiB");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0MiB");
+    }
+
+    #[test]
+    fn displays_stats() {
+        let stats = AllocStats {
+            allocations: 12,
+            bytes: 2048,
+            peak_bytes: 100,
+        };
+        assert_eq!(stats.to_string(), "12 allocs, 2.0KiB total, 100B peak");
+    }
+}
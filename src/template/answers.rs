@@ -0,0 +1,190 @@
+/// Answers accepted by advent of code, recorded in `data/answers.toml` when submitting via `solve --submit`.
+///
+/// ```toml
+/// [2023]
+/// 01-1 = "54632"
+/// 01-2 = "54019"
+/// ```
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+use crate::template::config::config;
+use crate::{Day, Part, PuzzleKey, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "invalid answers file: {e}"),
+            Error::IO(e) => write!(f, "could not access answers file: {e}"),
+        }
+    }
+}
+
+pub type Answers = BTreeMap<PuzzleKey, String>;
+
+#[must_use]
+pub fn get_answers_path() -> PathBuf {
+    config().data_dir.join("answers.toml")
+}
+
+fn parse_key(year: Year, key: &str) -> Option<PuzzleKey> {
+    let (day, part) = key.split_once('-')?;
+
+    Some(PuzzleKey {
+        year,
+        day: day.parse::<Day>().ok()?,
+        part: part.parse::<Part>().ok()?,
+    })
+}
+
+/// Parses the contents of an answers file.
+pub fn parse_answers(s: &str) -> Result<Answers, Error> {
+    let table: toml::Table = s
+        .parse()
+        .map_err(|e: toml::de::Error| Error::Parser(e.to_string()))?;
+
+    let mut answers = Answers::new();
+
+    for (year, section) in table {
+        let year: Year = year
+            .parse()
+            .map_err(|_| Error::Parser(format!("`{year}` is not a year of advent")))?;
+
+        let section = section
+            .as_table()
+            .ok_or_else(|| Error::Parser(format!("`{year}` must be a table")))?;
+
+        for (key, answer) in section {
+            let key = parse_key(year, key).ok_or_else(|| {
+                Error::Parser(format!(
+                    "expected a key like `01-1` in `{year}`, found `{key}`"
+                ))
+            })?;
+
+            let answer = match answer {
+                toml::Value::String(x) => x.clone(),
+                toml::Value::Integer(x) => x.to_string(),
+                x => {
+                    return Err(Error::Parser(format!(
+                        "`{key}` has an invalid answer `{x}`"
+                    )))
+                }
+            };
+
+            answers.insert(key, answer);
+        }
+    }
+
+    Ok(answers)
+}
+
+/// Formats answers as the contents of an answers file.
+#[must_use]
+pub fn format_answers(answers: &Answers) -> String {
+    let mut sections: Vec<String> = vec![];
+    let mut current = None;
+
+    for (key, answer) in answers {
+        if current != Some(key.year) {
+            current = Some(key.year);
+            sections.push(format!("[{}]\n", key.year));
+        }
+
+        if let Some(section) = sections.last_mut() {
+            section.push_str(&format!(
+                "{}-{} = {}\n",
+                key.day,
+                key.part,
+                toml::Value::String(answer.clone())
+            ));
+        }
+    }
+
+    sections.join("\n")
+}
+
+/// Reads the recorded answers. Returns no answers if none were recorded yet.
+pub fn read_answers() -> Result<Answers, Error> {
+    let path = env::current_dir()?.join(get_answers_path());
+
+    if !path.exists() {
+        return Ok(Answers::new());
+    }
+
+    parse_answers(&fs::read_to_string(path)?)
+}
+
+/// Records an accepted answer, replacing an earlier answer for the same part.
+pub fn record_answer(key: PuzzleKey, answer: &str) -> Result<(), Error> {
+    let mut answers = read_answers()?;
+    answers.insert(key, answer.to_string());
+
+    let path = env::current_dir()?.join(get_answers_path());
+    fs::write(path, format_answers(&answers))?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_answers, parse_answers, Answers, Error};
+    use crate::{day, Part, PuzzleKey, Year};
+
+    fn key(year: u16, day: crate::Day, part: Part) -> PuzzleKey {
+        PuzzleKey {
+            year: Year::new(year).unwrap(),
+            day,
+            part,
+        }
+    }
+
+    #[test]
+    fn parses_answers() {
+        let answers =
+            parse_answers("[2023]\n01-1 = \"142\"\n01-2 = 281\n\n[2022]\n25-1 = \"2=-1\"\n")
+                .unwrap();
+
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers[&key(2023, day!(1), Part::Two)], "281");
+        assert_eq!(answers[&key(2022, day!(25), Part::One)], "2=-1");
+    }
+
+    #[test]
+    fn round_trips_answers() {
+        let mut answers = Answers::new();
+        answers.insert(key(2023, day!(2), Part::One), "8".into());
+        answers.insert(key(2022, day!(1), Part::Two), "abc".into());
+
+        let formatted = format_answers(&answers);
+        assert_eq!(
+            formatted,
+            "[2022]\n01-2 = \"abc\"\n\n[2023]\n02-1 = \"8\"\n"
+        );
+        assert_eq!(parse_answers(&formatted).unwrap(), answers);
+    }
+
+    #[test]
+    fn rejects_invalid_answers() {
+        for s in [
+            "[1999]\n01-1 = 1",
+            "[2023]\n26-1 = 1",
+            "[2023]\n01-3 = 1",
+            "[2023]\n01 = 1",
+        ] {
+            assert!(matches!(parse_answers(s), Err(Error::Parser(_))), "{s}");
+        }
+    }
+}
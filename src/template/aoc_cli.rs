@@ -1,10 +1,16 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
+    env,
     fmt::Display,
+    fs,
+    path::{Path, PathBuf},
     process::{Command, Output, Stdio},
 };
 
-use crate::Day;
+use crate::template::config::config;
+use crate::template::encryption;
+use crate::template::puzzle;
+use crate::{Day, PuzzleKey, Year};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -46,12 +52,13 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
             puzzle_path,
         ],
         day,
+        config().year(),
     );
 
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
+pub fn download(day: Day, year: Year) -> Result<Output, AocCommandError> {
     let input_path = get_input_path(day);
     let puzzle_path = get_puzzle_path(day);
 
@@ -65,42 +72,108 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
             puzzle_path.to_string(),
         ],
         day,
+        Some(year),
     );
 
     let output = call_aoc_cli(&args)?;
     println!("---");
     println!("🎄 Successfully wrote input to \"{}\".", &input_path);
     println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    encrypt_input(&PathBuf::from(input_path));
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Replaces a downloaded input with its encrypted form if an input key is set.
+/// On failure, the plain text is kept, so that the download is not lost.
+fn encrypt_input(input_path: &Path) {
+    let key = match encryption::load_key() {
+        Ok(Some(key)) => key,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("⚠️ Could not encrypt input: {e}");
+            return;
+        }
+    };
+
+    match encryption::encrypt_file(&key, input_path).and_then(|encrypted_path| {
+        fs::remove_file(input_path)?;
+        Ok(encrypted_path)
+    }) {
+        Ok(encrypted_path) => {
+            println!("🔒 Encrypted input to \"{}\".", encrypted_path.display());
+        }
+        Err(e) => eprintln!("⚠️ Could not encrypt input: {e}"),
+    }
+}
+
+pub fn submit(key: PuzzleKey, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
-    args.push(part.to_string());
-    args.push(result.to_string());
-    call_aoc_cli(&args)
+    let mut args = session_args();
+    args.extend([
+        "--year".into(),
+        key.year.to_string(),
+        "--day".into(),
+        key.day.to_string(),
+        "submit".into(),
+        key.part.to_string(),
+        result.to_string(),
+    ]);
+
+    // stdout is captured to find out whether the answer was accepted, then echoed.
+    let output = Command::new("aoc")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
+
+/// Checks whether the output of `submit` reports the answer as correct.
+#[must_use]
+pub fn is_accepted(output: &Output) -> bool {
+    String::from_utf8_lossy(&output.stdout).contains("That's the right answer")
 }
 
 fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    config()
+        .inputs_dir()
+        .join(format!("{day}.txt"))
+        .to_string_lossy()
+        .into()
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    puzzle::get_puzzle_path(day).to_string_lossy().into()
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+/// Passes the session cookie file of the project config, expanding a leading `~/`.
+fn session_args() -> Vec<String> {
+    let Some(path) = &config().session_file else {
+        return vec![];
+    };
+
+    let path = match (path.strip_prefix("~"), env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.clone(),
+    };
+
+    vec!["--session-file".into(), path.to_string_lossy().into()]
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+/// Without a year, aoc-cli picks the year of the latest advent.
+fn build_args(command: &str, args: &[String], day: Day, year: Option<Year>) -> Vec<String> {
     let mut cmd_args = args.to_vec();
+    cmd_args.append(&mut session_args());
 
-    if let Some(year) = get_year() {
+    if let Some(year) = year {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
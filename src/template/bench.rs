@@ -0,0 +1,27 @@
+/// Hooks for external benchmark harnesses like `benches/solutions.rs`.
+/// The [`solution!`](crate::solution) macro generates a `register` function in every solution,
+/// which passes the day's steps and its real input to a [`Registry`].
+use std::env;
+
+use crate::template::config::config;
+use crate::template::encryption;
+use crate::Day;
+
+pub trait Registry {
+    /// Benchmarks one step of a day, e.g. `part_one`, with the given input.
+    fn bench<I: Copy, T>(&mut self, day: Day, step: &str, input: I, func: impl Fn(I) -> T);
+
+    /// Called for days that cannot be benchmarked, e.g. because their input is missing.
+    fn skip(&mut self, day: Day, reason: &str);
+}
+
+/// Reads the real input of a day, returns `None` if it was not downloaded yet.
+#[must_use]
+pub fn read_input(day: Day) -> Option<String> {
+    let path = env::current_dir()
+        .ok()?
+        .join(config().inputs_dir())
+        .join(format!("{day}.txt"));
+
+    encryption::read_input_file(&path).ok()
+}
@@ -0,0 +1,828 @@
+/// Declarative definition of the command-line interface.
+///
+/// The definitions are used to validate arguments before they are parsed with `pico_args`, to print help
+/// and to generate shell completions. Options that are shared between `solve`, `all` and the solution
+/// binaries use the same definition, so they behave the same everywhere.
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+
+/// A positional argument.
+pub struct Positional {
+    pub name: &'static str,
+    pub required: bool,
+    pub help: &'static str,
+    /// Values offered by shell completions.
+    pub values: &'static [&'static str],
+}
+
+/// A flag, or an option that takes a value if `value` is set.
+pub struct Arg {
+    pub name: &'static str,
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+}
+
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub positionals: &'static [Positional],
+    pub options: &'static [Arg],
+}
+
+const DAY_VALUES: &[&str] = &[
+    "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15", "16", "17",
+    "18", "19", "20", "21", "22", "23", "24", "25", "today",
+];
+
+const DAY: Positional = Positional {
+    name: "day",
+    required: true,
+    help: "Day number (1-25), or `today` during advent",
+    values: DAY_VALUES,
+};
+
+pub const RELEASE: Arg = Arg {
+    name: "--release",
+    value: None,
+    help: "Build the solutions with optimizations",
+};
+
+/// Turns off `defaults.release` of the project config for one run.
+pub const NO_RELEASE: Arg = Arg {
+    name: "--no-release",
+    value: None,
+    help: "Build without optimizations, even if `defaults.release` is set",
+};
+
+pub const TIME: Arg = Arg {
+    name: "--time",
+    value: None,
+    help: "Benchmark each part instead of running it once",
+};
+
+pub const NO_TIME: Arg = Arg {
+    name: "--no-time",
+    value: None,
+    help: "Run each part once, even if `defaults.time` is set",
+};
+
+pub const STABLE: Arg = Arg {
+    name: "--stable",
+    value: None,
+    help: "Benchmark on a pinned core with raised priority and warm-up runs, implies `--time`",
+};
+
+pub const PROFILE: Arg = Arg {
+    name: "--profile",
+    value: None,
+    help: "Sample each part and write a flamegraph to `target/aoc-profiles` (Linux only)",
+};
+
+pub const ALLOCS: Arg = Arg {
+    name: "--allocs",
+    value: None,
+    help: "Track allocations and peak memory of each part",
+};
+
+pub const NO_ALLOCS: Arg = Arg {
+    name: "--no-allocs",
+    value: None,
+    help: "Do not track allocations, even if `defaults.allocs` is set",
+};
+
+pub const TIMEOUT: Arg = Arg {
+    name: "--timeout",
+    value: Some("SECONDS"),
+    help: "Abort parts that run longer than the time budget",
+};
+
+pub const SUBMIT: Arg = Arg {
+    name: "--submit",
+    value: Some("PART"),
+    help: "Submit the answer of part 1 or 2 via aoc-cli",
+};
+
+pub const VARIANT: Arg = Arg {
+    name: "--variant",
+    value: Some("NAME"),
+    help: "Run the named variant of the parts instead of the default implementation",
+};
+
+pub const CROSSCHECK: Arg = Arg {
+    name: "--crosscheck",
+    value: None,
+    help: "Compare the answers of all variants on the examples and the input",
+};
+
+pub const ALL_INPUTS: Arg = Arg {
+    name: "--all-inputs",
+    value: None,
+    help: "Run the parts on every named input in `data/inputs/DD/` and check their answers",
+};
+
+pub const EXAMPLES: Arg = Arg {
+    name: "--examples",
+    value: None,
+    help: "Check the parts against the example manifest instead of running them",
+};
+
+pub const WATCH: Arg = Arg {
+    name: "--watch",
+    value: None,
+    help: "Run again whenever the solution, input or examples change",
+};
+
+pub const EXPORT: Arg = Arg {
+    name: "--export",
+    value: Some("FILE"),
+    help: "Write the timings to a `.json` or `.csv` file, requires `--time`",
+};
+
+pub const DAYS: Arg = Arg {
+    name: "--days",
+    value: Some("LIST"),
+    help: "Comma-separated days to compare, e.g. `1,6`. All days by default",
+};
+
+pub const THRESHOLD: Arg = Arg {
+    name: "--threshold",
+    value: Some("PERCENT"),
+    help: "Smallest significant slowdown that counts as a regression. Default: 5",
+};
+
+pub const DECRYPT_FORCE: Arg = Arg {
+    name: "--force",
+    value: None,
+    help: "Overwrite inputs that differ from their encrypted files",
+};
+
+pub const WAIT: Arg = Arg {
+    name: "--wait",
+    value: None,
+    help: "Wait for the puzzle to unlock, then download it",
+};
+
+pub const KEEP: Arg = Arg {
+    name: "--keep",
+    value: None,
+    help: "Keep the plain text inputs",
+};
+
+pub const NEW_KEY: Arg = Arg {
+    name: "--new-key",
+    value: None,
+    help: "Create a new input key file first",
+};
+
+pub const SELECT: Arg = Arg {
+    name: "--select",
+    value: Some("LIST"),
+    help: "Save the code blocks with these numbers, e.g. `1,3`",
+};
+
+pub const EXTRACT_FORCE: Arg = Arg {
+    name: "--force",
+    value: None,
+    help: "Overwrite existing example files and answers",
+};
+
+pub const REFRESH: Arg = Arg {
+    name: "--refresh",
+    value: None,
+    help: "Fetch the description via aoc-cli, even if it was downloaded",
+};
+
+pub const LEADERBOARD: Arg = Arg {
+    name: "--leaderboard",
+    value: Some("FILE"),
+    help: "Take the stars from a private leaderboard JSON file instead",
+};
+
+pub const MEMBER: Arg = Arg {
+    name: "--member",
+    value: Some("ID"),
+    help: "Member of the leaderboard, its owner by default",
+};
+
+pub const TEMPLATE: Arg = Arg {
+    name: "--template",
+    value: Some("NAME"),
+    help: "Use the template `.aoc/templates/NAME.rs.tpl`",
+};
+
+pub const TYPE: Arg = Arg {
+    name: "--type",
+    value: Some("TYPE[,TYPE]"),
+    help: "Return types of the parts, `u32` by default",
+};
+
+pub const SCAFFOLD_FORCE: Arg = Arg {
+    name: "--force",
+    value: None,
+    help: "Replace an existing module, keeping a backup",
+};
+
+pub const SCAFFOLD_EXAMPLES: Arg = Arg {
+    name: "--examples",
+    value: Some("N"),
+    help: "Create the numbered example files DD-1.txt to DD-N.txt",
+};
+
+pub const DOWNLOAD: Arg = Arg {
+    name: "--download",
+    value: None,
+    help: "Download the input and puzzle description via aoc-cli",
+};
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "all",
+        about: "Run the solutions of all days",
+        positionals: &[],
+        options: &[
+            RELEASE, NO_RELEASE, TIME, NO_TIME, STABLE, ALLOCS, NO_ALLOCS, TIMEOUT, EXPORT,
+        ],
+    },
+    Command {
+        name: "aoc-config",
+        about: "Show the project config, or set one of its settings",
+        positionals: &[
+            Positional {
+                name: "action",
+                required: true,
+                help: "`show` or `set`",
+                values: &["show", "set"],
+            },
+            Positional {
+                name: "key",
+                required: false,
+                help: "Setting to change, e.g. `bench.budget_ms`",
+                values: &[],
+            },
+            Positional {
+                name: "value",
+                required: false,
+                help: "New value of the setting",
+                values: &[],
+            },
+        ],
+        options: &[],
+    },
+    Command {
+        name: "bench-compare",
+        about: "Compare the timings of a git revision with the working tree",
+        positionals: &[Positional {
+            name: "rev",
+            required: true,
+            help: "Git revision to compare against, e.g. `main` or `HEAD~1`",
+            values: &[],
+        }],
+        options: &[STABLE, DAYS, THRESHOLD],
+    },
+    Command {
+        name: "completions",
+        about: "Print the completion script for a shell",
+        positionals: &[Positional {
+            name: "shell",
+            required: true,
+            help: "One of bash, zsh or fish",
+            values: &["bash", "zsh", "fish"],
+        }],
+        options: &[],
+    },
+    Command {
+        name: "crosscheck",
+        about: "Compare the answers of all variants of a day",
+        positionals: &[DAY],
+        options: &[RELEASE, NO_RELEASE],
+    },
+    Command {
+        name: "decrypt-inputs",
+        about: "Decrypt the encrypted inputs next to their encrypted files",
+        positionals: &[],
+        options: &[DECRYPT_FORCE],
+    },
+    Command {
+        name: "download",
+        about: "Download the input and puzzle description of a day via aoc-cli",
+        positionals: &[DAY],
+        options: &[WAIT],
+    },
+    Command {
+        name: "encrypt-inputs",
+        about: "Encrypt the inputs with the input key, so that they can be committed",
+        positionals: &[],
+        options: &[KEEP, NEW_KEY],
+    },
+    Command {
+        name: "examples",
+        about: "Check the solutions against their example manifests",
+        positionals: &[Positional {
+            required: false,
+            help: "Only check this day",
+            ..DAY
+        }],
+        options: &[],
+    },
+    Command {
+        name: "extract-examples",
+        about: "List the code blocks of a puzzle description and save them as examples",
+        positionals: &[DAY],
+        options: &[SELECT, EXTRACT_FORCE],
+    },
+    Command {
+        name: "inspect",
+        about: "Show statistics about the input of a day, e.g. the range of its integers",
+        positionals: &[DAY],
+        options: &[],
+    },
+    Command {
+        name: "read",
+        about: "Read the puzzle description of a day",
+        positionals: &[DAY],
+        options: &[REFRESH],
+    },
+    Command {
+        name: "readme",
+        about: "Update the stars table of the readme from the accepted answers",
+        positionals: &[],
+        options: &[LEADERBOARD, MEMBER],
+    },
+    Command {
+        name: "scaffold",
+        about: "Create the solution module, input and example files of a day",
+        positionals: &[DAY],
+        options: &[TEMPLATE, TYPE, SCAFFOLD_FORCE, SCAFFOLD_EXAMPLES, DOWNLOAD],
+    },
+    Command {
+        name: "solve",
+        about: "Run the solution of a day",
+        positionals: &[DAY],
+        options: &[
+            RELEASE, NO_RELEASE, TIME, NO_TIME, STABLE, ALLOCS, NO_ALLOCS, PROFILE, VARIANT,
+            ALL_INPUTS, TIMEOUT, SUBMIT, WATCH,
+        ],
+    },
+    Command {
+        name: "test-day",
+        about: "Run the tests of a day",
+        positionals: &[DAY],
+        options: &[RELEASE, NO_RELEASE, WATCH],
+    },
+];
+
+/// The arguments accepted by the solution binaries, passed by `solve` and `all`.
+pub const SOLUTION: Command = Command {
+    name: "DD",
+    about: "Run the solution of a day, usually via `cargo solve DD`",
+    positionals: &[],
+    options: &[
+        TIME, STABLE, PROFILE, VARIANT, ALL_INPUTS, TIMEOUT, SUBMIT, EXAMPLES, CROSSCHECK,
+    ],
+};
+
+#[must_use]
+pub fn find_command(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|x| x.name == name)
+}
+
+/// Parses the value of `--timeout`, a number of seconds. A timeout of zero would abort every part.
+pub fn parse_timeout(s: &str) -> Result<u64, &'static str> {
+    match s.parse() {
+        Ok(0) => Err("`--timeout` expects at least 1 second"),
+        Ok(x) => Ok(x),
+        Err(_) => Err("`--timeout` expects a number of seconds"),
+    }
+}
+
+/// Whether the arguments ask for help.
+pub fn is_help(args: &[String]) -> bool {
+    args.iter().any(|x| x == "--help" || x == "-h")
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    UnknownArgument(String),
+    MissingValue(&'static str),
+    MissingArgument(&'static str),
+}
+
+impl std::error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnknownArgument(x) => write!(f, "unexpected argument `{x}`"),
+            Error::MissingValue(x) => write!(f, "option `{x}` requires a value"),
+            Error::MissingArgument(x) => write!(f, "missing argument <{x}>"),
+        }
+    }
+}
+
+/// Checks that the arguments of a command are known and complete.
+/// Values are validated when they are parsed.
+pub fn validate(command: &Command, args: &[String]) -> Result<(), Error> {
+    let mut positionals = 0;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        if arg.starts_with('-') && arg.parse::<i64>().is_err() {
+            let option = command
+                .options
+                .iter()
+                .find(|x| x.name == arg)
+                .ok_or_else(|| Error::UnknownArgument(arg.clone()))?;
+
+            if option.value.is_some() && iter.next().is_none() {
+                return Err(Error::MissingValue(option.name));
+            }
+        } else if positionals < command.positionals.len() {
+            positionals += 1;
+        } else {
+            return Err(Error::UnknownArgument(arg.clone()));
+        }
+    }
+
+    match command.positionals[positionals..]
+        .iter()
+        .find(|x| x.required)
+    {
+        Some(positional) => Err(Error::MissingArgument(positional.name)),
+        None => Ok(()),
+    }
+}
+
+fn format_usage(command: &Command, program: &str) -> String {
+    let mut usage = format!("{program} {}", command.name);
+
+    for positional in command.positionals {
+        if positional.required {
+            let _ = write!(usage, " <{}>", positional.name);
+        } else {
+            let _ = write!(usage, " [{}]", positional.name);
+        }
+    }
+
+    if !command.options.is_empty() {
+        usage.push_str(" [options]");
+    }
+
+    usage
+}
+
+fn format_arg(arg: &Arg) -> String {
+    match arg.value {
+        Some(value) => format!("{} <{value}>", arg.name),
+        None => arg.name.to_string(),
+    }
+}
+
+/// Formats the help text of a command.
+#[must_use]
+pub fn format_help(command: &Command, program: &str) -> String {
+    let mut help = format!(
+        "{}\n\nUsage: {}\n",
+        command.about,
+        format_usage(command, program)
+    );
+
+    if !command.positionals.is_empty() {
+        help.push_str("\nArguments:\n");
+    }
+
+    for positional in command.positionals {
+        let _ = writeln!(
+            help,
+            "  {:<24}{}",
+            format!("<{}>", positional.name),
+            positional.help
+        );
+    }
+
+    help.push_str("\nOptions:\n");
+
+    for arg in command.options {
+        let _ = writeln!(help, "  {:<24}{}", format_arg(arg), arg.help);
+    }
+
+    let _ = writeln!(help, "  {:<24}Print help", "-h, --help");
+    help
+}
+
+/// Formats the list of commands.
+#[must_use]
+pub fn format_overview() -> String {
+    let mut help = String::from("Usage: cargo <command> [arguments]\n\nCommands:\n");
+
+    for command in COMMANDS {
+        let _ = writeln!(help, "  {:<24}{}", command.name, command.about);
+    }
+
+    help.push_str("\nSee `cargo <command> --help` for the arguments of a command.\n");
+    help
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = ShellFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Self::Bash),
+            "zsh" => Ok(Self::Zsh),
+            "fish" => Ok(Self::Fish),
+            _ => Err(ShellFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Shell`].
+#[derive(Debug)]
+pub struct ShellFromStrError;
+
+impl std::error::Error for ShellFromStrError {}
+
+impl Display for ShellFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of bash, zsh or fish")
+    }
+}
+
+/// Generates a completion script for the commands, which are run as cargo aliases.
+/// Other cargo subcommands are passed on to the completion installed for cargo, if any.
+#[must_use]
+pub fn completions(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash_completions(),
+        Shell::Zsh => zsh_completions(),
+        Shell::Fish => fish_completions(),
+    }
+}
+
+fn command_names() -> String {
+    COMMANDS
+        .iter()
+        .map(|x| x.name)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Finds the completion that was registered for cargo before, and calls it for other subcommands.
+/// bash-completion loads completions on first use, which replaces ours, so it is registered again.
+const BASH_PREVIOUS_COMPLETION: &str = r#"_aoc_cargo_find_previous() {
+    complete -p cargo 2>/dev/null | sed -n 's/.* -F \([^ ]*\) .*/\1/p'
+}
+
+# sourcing the script twice must not make the completion call itself.
+if [[ $(_aoc_cargo_find_previous) != _aoc_cargo ]]; then
+    _aoc_cargo_previous=$(_aoc_cargo_find_previous)
+fi
+
+_aoc_cargo_previous_completion() {
+    if [[ -z $_aoc_cargo_previous ]]; then
+        if declare -F _comp_load >/dev/null; then
+            _comp_load cargo
+        elif declare -F _completion_loader >/dev/null; then
+            _completion_loader cargo
+        fi
+        _aoc_cargo_previous=$(_aoc_cargo_find_previous)
+        [[ $_aoc_cargo_previous == _aoc_cargo ]] && _aoc_cargo_previous=
+        complete -F _aoc_cargo cargo
+    fi
+
+    if [[ -n $_aoc_cargo_previous ]]; then
+        "$_aoc_cargo_previous" "$@"
+    fi
+}
+
+"#;
+
+fn bash_completions() -> String {
+    let mut script = String::from(BASH_PREVIOUS_COMPLETION);
+    script.push_str(
+        "_aoc_cargo() {\n    local cur=\"${COMP_WORDS[COMP_CWORD]}\" prev=\"${COMP_WORDS[COMP_CWORD-1]}\" words\n\n    case \"${COMP_WORDS[1]}\" in\n",
+    );
+
+    for command in COMMANDS {
+        let mut words: Vec<&str> = command.options.iter().map(|x| x.name).collect();
+        for positional in command.positionals {
+            words.extend(positional.values);
+        }
+
+        let _ = writeln!(
+            script,
+            "        {}) words=\"{}\" ;;",
+            command.name,
+            words.join(" ")
+        );
+    }
+
+    let _ = write!(
+        script,
+        "        *)\n            _aoc_cargo_previous_completion \"$@\"\n            if [[ $COMP_CWORD -eq 1 ]]; then\n                COMPREPLY+=($(compgen -W \"{}\" -- \"$cur\"))\n            fi\n            return\n            ;;\n    esac\n\n",
+        command_names()
+    );
+
+    let options: Vec<&str> = COMMANDS
+        .iter()
+        .flat_map(|x| x.options)
+        .filter(|x| x.value.is_some())
+        .map(|x| x.name)
+        .collect();
+
+    let _ = write!(
+        script,
+        "    # the value of an option can not be completed.\n    case \"$prev\" in\n        {}) return ;;\n    esac\n\n    COMPREPLY=($(compgen -W \"$words\" -- \"$cur\"))\n}}\n\ncomplete -F _aoc_cargo cargo\n",
+        dedup(options).join("|")
+    );
+
+    script
+}
+
+fn zsh_completions() -> String {
+    // the completion that was registered for cargo before, e.g. `_cargo`, is called for other subcommands.
+    let mut script = String::from(
+        "#compdef cargo\n\nif [[ ${_comps[cargo]} != _aoc_cargo ]]; then\n    typeset -g _aoc_cargo_previous=${_comps[cargo]}\nfi\n\n_aoc_cargo() {\n    case $words[2] in\n",
+    );
+
+    for command in COMMANDS {
+        let mut specs: Vec<String> = command
+            .options
+            .iter()
+            .map(|arg| match arg.value {
+                Some(value) => format!("'{}[{}]:{value}:'", arg.name, escape_zsh(arg.help)),
+                None => format!("'{}[{}]'", arg.name, escape_zsh(arg.help)),
+            })
+            .collect();
+
+        for (i, positional) in command.positionals.iter().enumerate() {
+            let optional = if positional.required { "" } else { ":" };
+            specs.push(format!(
+                "'{}{optional}:{}:({})'",
+                i + 1,
+                positional.name,
+                positional.values.join(" ")
+            ));
+        }
+
+        let _ = writeln!(
+            script,
+            "        {})\n            shift words; (( CURRENT-- ))\n            _arguments {}\n            ;;",
+            command.name,
+            specs.join(" ")
+        );
+    }
+
+    let _ = write!(
+        script,
+        "        *)\n            if (( CURRENT == 2 )); then\n                compadd {}\n            fi\n            [[ -n $_aoc_cargo_previous ]] && $_aoc_cargo_previous \"$@\"\n            ;;\n    esac\n}}\n\ncompdef _aoc_cargo cargo\n",
+        command_names()
+    );
+
+    script
+}
+
+fn fish_completions() -> String {
+    let mut script = String::new();
+
+    for command in COMMANDS {
+        let _ = writeln!(
+            script,
+            "complete -c cargo -n '__fish_use_subcommand' -f -a '{}' -d '{}'",
+            command.name,
+            escape_fish(command.about)
+        );
+    }
+
+    for command in COMMANDS {
+        let condition = format!("__fish_seen_subcommand_from {}", command.name);
+
+        for positional in command.positionals.iter().filter(|x| !x.values.is_empty()) {
+            let _ = writeln!(
+                script,
+                "complete -c cargo -n '{condition}' -f -a '{}' -d '{}'",
+                positional.values.join(" "),
+                escape_fish(positional.help)
+            );
+        }
+
+        for arg in command.options {
+            let require = if arg.value.is_some() { " -r" } else { "" };
+            let _ = writeln!(
+                script,
+                "complete -c cargo -n '{condition}' -l '{}'{require} -d '{}'",
+                arg.name.trim_start_matches("--"),
+                escape_fish(arg.help)
+            );
+        }
+    }
+
+    script
+}
+
+fn dedup(mut items: Vec<&str>) -> Vec<&str> {
+    items.sort_unstable();
+    items.dedup();
+    items
+}
+
+fn escape_zsh(s: &str) -> String {
+    s.replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(':', "\\:")
+}
+
+fn escape_fish(s: &str) -> String {
+    s.replace('\'', "\\'")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        completions, find_command, format_help, parse_timeout, validate, Error, Shell, COMMANDS,
+    };
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn validates_arguments() {
+        let solve = find_command("solve").unwrap();
+
+        assert_eq!(validate(solve, &args("1 --release --timeout 5")), Ok(()));
+        assert_eq!(
+            validate(solve, &args("1 --relase")),
+            Err(Error::UnknownArgument("--relase".into()))
+        );
+        assert_eq!(
+            validate(solve, &args("1 2")),
+            Err(Error::UnknownArgument("2".into()))
+        );
+        assert_eq!(
+            validate(solve, &args("1 --timeout")),
+            Err(Error::MissingValue("--timeout"))
+        );
+        assert_eq!(
+            validate(solve, &args("--release")),
+            Err(Error::MissingArgument("day"))
+        );
+    }
+
+    #[test]
+    fn rejects_zero_timeout() {
+        assert_eq!(parse_timeout("10"), Ok(10));
+        assert!(parse_timeout("0").is_err());
+        assert!(parse_timeout("-1").is_err());
+        assert!(parse_timeout("1.5").is_err());
+    }
+
+    #[test]
+    fn validates_optional_positionals() {
+        let examples = find_command("examples").unwrap();
+        assert_eq!(validate(examples, &args("")), Ok(()));
+        assert_eq!(validate(examples, &args("3")), Ok(()));
+
+        let all = find_command("all").unwrap();
+        assert_eq!(
+            validate(all, &args("3")),
+            Err(Error::UnknownArgument("3".into()))
+        );
+    }
+
+    #[test]
+    fn formats_help() {
+        let help = format_help(find_command("solve").unwrap(), "cargo");
+        assert!(
+            help.starts_with("Run the solution of a day\n\nUsage: cargo solve <day> [options]\n")
+        );
+        assert!(help.contains("  --timeout <SECONDS>     Abort parts"));
+    }
+
+    #[test]
+    fn does_not_shadow_cargo_config() {
+        assert!(find_command("config").is_none());
+        let help = format_help(find_command("aoc-config").unwrap(), "cargo");
+        assert!(help.contains("Usage: cargo aoc-config <action>"));
+        assert!(!completions(Shell::Bash).contains(" config)"));
+    }
+
+    #[test]
+    fn completes_all_commands() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = completions(shell);
+            for command in COMMANDS {
+                assert!(script.contains(command.name));
+            }
+        }
+    }
+}
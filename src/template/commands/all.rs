@@ -1,13 +1,49 @@
-use std::io;
+use std::path::Path;
+use std::time::Duration;
+use std::{io, process};
 
 use crate::template::{
+    export::{self, Environment, Format},
     readme_benchmarks::{self, Timings},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
-
-pub fn handle(is_release: bool, is_timed: bool) {
+use crate::{all_days, Day, SystemClock};
+
+pub fn handle(
+    is_release: bool,
+    is_timed: bool,
+    is_stable: bool,
+    track_allocs: bool,
+    timeout: Option<u64>,
+    export: Option<&Path>,
+) {
     let mut timings: Vec<Timings> = vec![];
+    // the settings of `--stable`, as reported by the solutions.
+    let mut stable_settings: Option<String> = None;
+    let timeout = timeout.map(Duration::from_secs);
+
+    // check the export upfront, rather than after running all solutions.
+    let export = export.map(|path| {
+        if !is_timed {
+            eprintln!("`--export` requires `--time`.");
+            process::exit(1);
+        }
+
+        let Some(format) = Format::from_path(path) else {
+            eprintln!(
+                "Cannot export to \"{}\": expected a `.json` or `.csv` file.",
+                path.display()
+            );
+            process::exit(1);
+        };
+
+        (path, format)
+    });
+
+    // build all solutions upfront so that compile times do not count towards the time budget.
+    if timeout.is_some() {
+        child_commands::build_solutions(is_release, track_allocs).unwrap();
+    }
 
     all_days().for_each(|day| {
         if day > 1 {
@@ -17,26 +53,64 @@ pub fn handle(is_release: bool, is_timed: bool) {
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = child_commands::run_solution(
+            day,
+            is_timed,
+            is_stable,
+            is_release,
+            track_allocs,
+            timeout,
+        )
+        .unwrap();
+
+        if stable_settings.is_none() {
+            stable_settings = child_commands::parse_stable_settings(&output.lines);
+        }
 
-        if output.is_empty() {
+        if output.lines.is_empty() && !output.timed_out {
             println!("Not solved.");
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
+            let mut val = child_commands::parse_exec_time(&output.lines, day);
+
+            if output.timed_out {
+                // NOTE: `timed_out` is only ever set when a time budget was passed.
+                let timeout = timeout.unwrap_or_default();
+                println!("Day {day}: TIMEOUT after {timeout:?}");
+                child_commands::mark_timed_out(&mut val, timeout);
+            }
+
             timings.push(val);
         }
     });
 
     if is_timed {
-        let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+        let total_millis = timings
+            .iter()
+            .map(Timings::total)
+            .sum::<Duration>()
+            .as_secs_f64()
+            * 1000.0;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
+        if let Some((path, format)) = export {
+            let mut environment = Environment::collect(is_release, track_allocs, &SystemClock);
+            environment.stable = stable_settings;
+
+            match export::export(path, format, &timings, &environment) {
+                Ok(()) => println!("Exported timings to \"{}\".", path.display()),
+                Err(e) => eprintln!("Failed to export timings: {e}"),
+            }
+        }
+
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
+            match readme_benchmarks::update(timings) {
+                Ok(path) => println!(
+                    "Successfully updated \"{}\" with benchmarks.",
+                    path.display()
+                ),
+                Err(e) => {
+                    eprintln!("Failed to update readme with benchmarks: {e}");
                 }
             }
         }
@@ -63,23 +137,93 @@ pub fn get_path_for_bin(day: Day) -> String {
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
+pub(super) mod child_commands {
     use super::{get_path_for_bin, Error};
+    use crate::template::cli;
+    use crate::template::config::config;
+    use crate::template::readme_benchmarks::{self, Measurement, Timing};
+    use crate::template::runner::RECORDS_ENV;
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
+    /// Output captured from a solution bin.
+    pub struct SolutionOutput {
+        pub lines: Vec<String>,
+        /// The child was killed because it exceeded its time budget.
+        pub timed_out: bool,
+    }
+
+    /// Build all solution bins without running them.
+    pub fn build_solutions(is_release: bool, track_allocs: bool) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet", "--bins"];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        if track_allocs {
+            args.extend(["--features", "alloc_tracking"]);
+        }
+
+        Command::new("cargo").args(&args).status()?;
+        Ok(())
+    }
+
+    /// The budget for a whole child process, derived from the budget for a single step.
+    /// Each step, i.e. the parse step and both parts, may take up to `timeout` for its first execution.
+    /// When benching, a step is executed until it spent the `[bench]` budget, but at least `min_samples`
+    /// times, and once more to count allocations. `--stable` adds a warm-up run to every sample.
+    fn child_budget(
+        timeout: Duration,
+        is_timed: bool,
+        is_stable: bool,
+        track_allocs: bool,
+    ) -> Duration {
+        let bounds = &config().bench;
+        let min_samples = u32::try_from(bounds.min_samples).unwrap_or(u32::MAX);
+        let bench = bounds.budget.max(timeout.saturating_mul(min_samples));
+
+        let mut per_step = if is_stable {
+            timeout + bench.saturating_mul(2)
+        } else if is_timed {
+            timeout + bench
+        } else {
+            timeout
+        };
+
+        if is_timed && track_allocs {
+            per_step += timeout;
+        }
+
+        // some slack for process startup, reading the input and samples that are slower than the first run.
+        per_step.saturating_mul(3 * 2) + Duration::from_secs(1)
+    }
+
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_stable: bool,
+        is_release: bool,
+        track_allocs: bool,
+        timeout: Option<Duration>,
+    ) -> Result<SolutionOutput, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(SolutionOutput {
+                lines: vec![],
+                timed_out: false,
+            });
         }
 
+        let timeout_str = timeout.map(|x| x.as_secs().to_string());
+
         let day_padded = day.to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
@@ -87,10 +231,24 @@ mod child_commands {
             args.push("--release");
         }
 
-        if is_timed {
+        if track_allocs {
+            args.extend(["--features", "alloc_tracking"]);
+        }
+
+        args.push("--");
+
+        if is_stable {
+            // mirror `--stable` flag to child invocations, it implies `--time`.
+            args.push(cli::STABLE.name);
+        } else if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push(cli::TIME.name);
+        }
+
+        if let Some(timeout_str) = &timeout_str {
+            // mirror `--timeout` flag to child invocations.
+            args.push(cli::TIMEOUT.name);
+            args.push(timeout_str);
         }
 
         // spawn child command with piped stdout/stderr.
@@ -98,6 +256,7 @@ mod child_commands {
 
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .env(RECORDS_ENV, "1")
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -105,106 +264,175 @@ mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
+        let stderr_thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
                 eprintln!("{}", line.unwrap());
             });
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let stdout_thread = thread::spawn(move || {
+            let mut output = vec![];
+            for line in stdout.lines() {
+                let line = line.unwrap();
+                // exact timings are only meant for parsing.
+                if !line.starts_with(readme_benchmarks::RECORD_PREFIX) {
+                    println!("{line}");
+                }
+                output.push(line);
+            }
+            output
+        });
+
+        // watch the child and kill it once it exceeds its time budget.
+        let deadline =
+            timeout.map(|x| Instant::now() + child_budget(x, is_timed, is_stable, track_allocs));
+        let mut timed_out = false;
+
+        while cmd.try_wait()?.is_none() {
+            if deadline.is_some_and(|x| Instant::now() > x) {
+                cmd.kill()?;
+                cmd.wait()?;
+                timed_out = true;
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        stderr_thread.join().unwrap();
+        let lines = stdout_thread.join().unwrap();
 
-        Ok(output)
+        Ok(SolutionOutput { lines, timed_out })
     }
 
+    /// Parses the timings of a solution's output. Exact timings from [`readme_benchmarks::parse_record`]
+    /// take precedence over the rounded ones of the human-readable lines, which older solution bins
+    /// print exclusively.
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
         let mut timings = super::Timings {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
-            total_nanos: 0_f64,
         };
 
         output
             .iter()
             .filter_map(|l| {
+                if let Some((part, measurement)) = readme_benchmarks::parse_record(l) {
+                    return Some((part, Timing::Measured(measurement)));
+                }
+
+                if let Some((part, timeout)) = l.split_once(": TIMEOUT after ") {
+                    return Some((part, Timing::TimedOut(parse_duration(timeout.trim())?)));
+                }
+
+                // a step that ran once, e.g. with `bench.min_samples = 1`, has no sample count.
                 if !l.contains(" samples)") {
-                    return None;
+                    let part = l.split(':').next()?;
+                    return Some((part, Timing::Measured(parse_single_run(l)?)));
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let Some(measurement) = parse_measurement(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, Timing::Measured(measurement)))
             })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
+            .for_each(|(part, timing)| {
+                if part.contains("Parse") {
+                    timings.parse = Some(timing);
+                } else if part.contains("Part 1") {
+                    timings.part_1 = Some(timing);
                 } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                    timings.part_2 = Some(timing);
                 }
-
-                timings.total_nanos += nanos;
             });
 
         timings
     }
 
+    /// The settings that a solution applied for `--stable`, e.g. `Stable: core 3, nice -10, interleaved warm-up`.
+    pub fn parse_stable_settings(output: &[String]) -> Option<String> {
+        output
+            .iter()
+            .find_map(|l| l.strip_prefix("Stable: "))
+            .map(|x| x.trim().to_string())
+    }
+
+    /// Mark the parts of a killed child that did not report a result as timed out.
+    pub fn mark_timed_out(timings: &mut super::Timings, timeout: Duration) {
+        timings.part_1.get_or_insert(Timing::TimedOut(timeout));
+        timings.part_2.get_or_insert(Timing::TimedOut(timeout));
+    }
+
+    /// Parses the timing of a benchmarked step, e.g. `(1.0ms ± 20.0µs, median 990.0µs @ 10 samples)`.
+    /// Only the mean and sample count are reported by older solution bins.
+    fn parse_measurement(line: &str) -> Option<Measurement> {
+        let timing = line.split(" samples)").next()?.rsplit_once('(')?.1;
+        let (stats, samples) = timing.rsplit_once('@')?;
+
+        let stat = |prefix: &str| {
+            stats
+                .split(", ")
+                .find_map(|x| x.split_once(prefix))
+                .and_then(|x| parse_duration(x.1.trim()))
+        };
+
+        Some(Measurement {
+            mean: parse_duration(stats.split_whitespace().next()?)?,
+            median: stat("median "),
+            stddev: stat("± "),
+            samples: samples.trim().parse().ok()?,
+            allocs: None,
+        })
+    }
+
+    /// Parses the timing of a step that ran once, e.g. `Part 1: 42 (1.0ms)`.
+    fn parse_single_run(line: &str) -> Option<Measurement> {
+        let (_, timing) = line.strip_suffix(')')?.rsplit_once(" (")?;
+        if !timing.ends_with('s') {
+            return None;
+        }
+
+        Some(Measurement {
+            mean: parse_duration(timing)?,
+            samples: 1,
+            ..Measurement::default()
+        })
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
+    /// Parses a duration in its debug format, e.g. `74.13ms`.
+    fn parse_duration(s: &str) -> Option<Duration> {
         // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
+        let nanos = match s {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
             s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
             s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
             s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
         }?;
 
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Some(Duration::from_nanos(nanos.round() as u64))
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_stable_settings};
+        use std::time::Duration;
 
         use crate::day;
+        use crate::template::allocator::AllocStats;
+        use crate::template::readme_benchmarks::{Measurement, Timing};
+
+        fn mean(timing: Option<Timing>) -> Duration {
+            timing.unwrap().measurement().unwrap().mean
+        }
 
         #[test]
         fn test_well_formed() {
@@ -216,9 +444,9 @@ mod child_commands {
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total(), Duration::from_nanos(74130074));
+            assert_eq!(mean(res.part_1), Duration::from_nanos(74));
+            assert_eq!(mean(res.part_2), Duration::from_micros(74130));
         }
 
         #[test]
@@ -231,9 +459,136 @@ mod child_commands {
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total(), Duration::from_millis(2100));
+            assert_eq!(mean(res.part_1), Duration::from_secs(2));
+            assert_eq!(mean(res.part_2), Duration::from_millis(100));
+        }
+
+        #[test]
+        fn test_parse_step() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: (1.5ms @ 666 samples)".into(),
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.total(), Duration::from_nanos(75630074));
+            assert_eq!(mean(res.parse), Duration::from_micros(1500));
+            assert_eq!(mean(res.part_1), Duration::from_nanos(74));
+            assert_eq!(mean(res.part_2), Duration::from_micros(74130));
+        }
+
+        #[test]
+        fn test_records() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (1.2ms ± 2.1µs, median 1.2ms @ 10 samples) [3 allocs, 1.5KiB total, 1.0KiB peak]"
+                        .into(),
+                    "@timing Part 1: mean_ns=1249013 median_ns=1248001 stddev_ns=2107 samples=10 allocs=3 alloc_bytes=1536 peak_bytes=1024"
+                        .into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+
+            assert_eq!(
+                res.part_1,
+                Some(Timing::Measured(Measurement {
+                    mean: Duration::from_nanos(1_249_013),
+                    median: Some(Duration::from_nanos(1_248_001)),
+                    stddev: Some(Duration::from_nanos(2_107)),
+                    samples: 10,
+                    allocs: Some(AllocStats {
+                        allocations: 3,
+                        bytes: 1536,
+                        peak_bytes: 1024,
+                    }),
+                }))
+            );
+
+            // older bins without records still report their rounded timings.
+            assert_eq!(mean(res.part_2), Duration::from_micros(74130));
+        }
+
+        #[test]
+        fn test_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.1ns ± 2.0ns, median 73.0ns @ 100000 samples)".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+
+            assert_eq!(
+                res.part_1,
+                Some(Timing::Measured(Measurement {
+                    mean: Duration::from_nanos(74),
+                    median: Some(Duration::from_nanos(73)),
+                    stddev: Some(Duration::from_nanos(2)),
+                    samples: 100000,
+                    allocs: None,
+                }))
+            );
+
+            let part_2 = res.part_2.unwrap();
+            let part_2 = part_2.measurement().unwrap();
+            assert_eq!(part_2.median, None);
+            assert_eq!(part_2.samples, 99999);
+        }
+
+        #[test]
+        fn test_single_sample() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: (1.5ms)".into(),
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Part 2: (a, b) (74.1ms)".into(),
+                    "Part 2: 10 samples in \"a.svg\", folded stacks in \"a.folded\"".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(mean(res.parse), Duration::from_micros(1500));
+            assert_eq!(
+                res.part_2.as_ref().unwrap().measurement().unwrap().samples,
+                1
+            );
+            assert_eq!(mean(res.part_2), Duration::from_micros(74100));
+        }
+
+        #[test]
+        fn test_timeout() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 10 (74.13ms @ 99999 samples)".into(),
+                    "Part 2: TIMEOUT after 5s".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.total(), Duration::from_micros(74130));
+            assert_eq!(mean(res.part_1), Duration::from_micros(74130));
+            assert_eq!(res.part_2, Some(Timing::TimedOut(Duration::from_secs(5))));
+            assert_eq!(res.part_2.unwrap().to_string(), "TIMEOUT after 5s");
+        }
+
+        #[test]
+        fn test_stable_settings() {
+            let output = [
+                "Stable: core 3, nice -10, interleaved warm-up".to_string(),
+                "Part 1: 10 (74.13ms @ 99999 samples)".into(),
+            ];
+            assert_eq!(
+                parse_stable_settings(&output).as_deref(),
+                Some("core 3, nice -10, interleaved warm-up")
+            );
+            assert_eq!(parse_stable_settings(&output[1..]), None);
         }
 
         #[test]
@@ -246,7 +601,7 @@ mod child_commands {
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.total(), Duration::ZERO);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
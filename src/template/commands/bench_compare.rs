@@ -0,0 +1,228 @@
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::{env, fmt, io};
+
+use crate::template::commands::all::{child_commands, get_path_for_bin};
+use crate::template::compare::{self, Verdict};
+use crate::template::config::config;
+use crate::template::encryption;
+use crate::template::readme_benchmarks::Timings;
+use crate::template::runner::RECORDS_ENV;
+use crate::template::{cli, ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, Day};
+
+/// Target directory for builds of the compared revision. It is kept between runs to cache dependencies.
+const TARGET_DIR: &str = "target/aoc-compare";
+
+#[derive(Debug)]
+pub enum Error {
+    Git(String),
+    Build(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Git(e) => write!(f, "git: {e}"),
+            Error::Build(e) => write!(f, "could not build {e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A detached git worktree that is removed when dropped.
+struct Worktree {
+    path: PathBuf,
+}
+
+impl Worktree {
+    fn add(commit: &str) -> Result<Self, Error> {
+        let path = env::temp_dir().join(format!("aoc-bench-compare-{}", process::id()));
+
+        git(&[
+            "worktree",
+            "add",
+            "--detach",
+            "--quiet",
+            &path.to_string_lossy(),
+            commit,
+        ])?;
+
+        Ok(Self { path })
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        let path = self.path.to_string_lossy();
+        if let Err(e) = git(&["worktree", "remove", "--force", &path]) {
+            eprintln!("Failed to remove worktree \"{path}\": {e}");
+        }
+    }
+}
+
+/// Runs git and returns its trimmed stdout.
+fn git(args: &[&str]) -> Result<String, Error> {
+    let output = Command::new("git").args(args).output()?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(Error::Git(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+/// Builds all solutions of the project in `dir` with optimizations.
+fn build(dir: &Path, target_dir: Option<&Path>, name: &str) -> Result<(), Error> {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["build", "--quiet", "--release", "--bins"])
+        .current_dir(dir);
+
+    if let Some(target_dir) = target_dir {
+        cmd.env("CARGO_TARGET_DIR", target_dir);
+    }
+
+    if cmd.status()?.success() {
+        Ok(())
+    } else {
+        Err(Error::Build(name.into()))
+    }
+}
+
+/// Runs a solution binary with `--time` and parses its timings. The binary runs in the current
+/// directory, so both revisions read the same inputs and config. Binaries that print exact
+/// timings are compared on those, older ones on their rounded output.
+fn time_solution(bin: &Path, day: Day, is_stable: bool) -> Result<Timings, Error> {
+    let flag = if is_stable { cli::STABLE } else { cli::TIME };
+
+    let output = Command::new(bin)
+        .arg(flag.name)
+        .env(RECORDS_ENV, "1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    let lines: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(String::from)
+        .collect();
+
+    if !output.status.success() {
+        eprintln!("Day {day} exited with {}.", output.status);
+    }
+
+    Ok(child_commands::parse_exec_time(&lines, day))
+}
+
+/// Times the days at both revisions and returns whether any step regressed.
+fn run(rev: &str, days: &[Day], is_stable: bool, threshold: f64) -> Result<bool, Error> {
+    let commit = git(&[
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("{rev}^{{commit}}"),
+    ])
+    .map_err(|_| Error::Git(format!("unknown revision \"{rev}\"")))?;
+
+    let worktree = Worktree::add(&commit)?;
+    let cwd = env::current_dir()?;
+    let old_target = cwd.join(TARGET_DIR);
+
+    println!("Building {rev}...");
+    build(&worktree.path, Some(&old_target), rev)?;
+    println!("Building working tree...");
+    build(&cwd, None, "the working tree")?;
+
+    let mut comparisons = vec![];
+
+    for &day in days {
+        let bin_path = get_path_for_bin(day);
+        let old_exists = worktree.path.join(&bin_path).exists();
+        let new_exists = Path::new(&bin_path).exists();
+
+        if !old_exists && !new_exists {
+            continue;
+        }
+
+        if !encryption::exists(&config().inputs_dir().join(format!("{day}.txt"))) {
+            println!("Skipping day {day}: input file is missing.");
+            continue;
+        }
+
+        println!("Timing day {day}...");
+
+        let empty = Timings {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+        };
+
+        // both revisions run back to back, so they see similar conditions on the machine.
+        let old = if old_exists {
+            time_solution(
+                &old_target.join("release").join(day.to_string()),
+                day,
+                is_stable,
+            )?
+        } else {
+            empty.clone()
+        };
+
+        let new = if new_exists {
+            time_solution(
+                &cwd.join("target/release").join(day.to_string()),
+                day,
+                is_stable,
+            )?
+        } else {
+            empty
+        };
+
+        comparisons.extend(compare::compare(&old, &new, threshold));
+    }
+
+    drop(worktree);
+
+    println!();
+    print!("{}", compare::format_table(&comparisons));
+
+    let (old_total, new_total) = compare::totals(&comparisons);
+    println!(
+        "\n{ANSI_BOLD}Total:{ANSI_RESET} {old_total:.1?} at {rev}, {new_total:.1?} in the working tree"
+    );
+
+    let regressions = comparisons
+        .iter()
+        .filter(|x| x.verdict == Verdict::Slower)
+        .count();
+
+    if regressions > 0 {
+        println!("{regressions} step(s) regressed.");
+    }
+
+    Ok(regressions > 0)
+}
+
+pub fn handle(rev: &str, days: Option<Vec<Day>>, is_stable: bool, threshold: Option<f64>) {
+    let days = days.unwrap_or_else(|| all_days().collect());
+    let threshold = threshold.unwrap_or(5.0) / 100.0;
+
+    match run(rev, &days, is_stable, threshold) {
+        Ok(false) => {}
+        Ok(true) => process::exit(1),
+        Err(e) => {
+            eprintln!("Failed to compare benchmarks: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+use crate::template::cli::{self, Shell};
+
+pub fn handle(shell: Shell) {
+    print!("{}", cli::completions(shell));
+}
@@ -0,0 +1,50 @@
+use std::path::Path;
+use std::{fs, process};
+
+use crate::template::config::{self, config, CONFIG_PATH, KEYS};
+
+pub enum Action {
+    Show,
+    Set { key: String, value: String },
+}
+
+pub fn handle(action: Action) {
+    match action {
+        Action::Show => show(),
+        Action::Set { key, value } => set(&key, &value),
+    }
+}
+
+/// Print the effective config, including defaults for missing settings.
+fn show() {
+    if Path::new(CONFIG_PATH).exists() {
+        println!("# effective config, loaded from {CONFIG_PATH}.");
+    } else {
+        println!("# {CONFIG_PATH} does not exist, showing the defaults.");
+    }
+
+    print!("{}", config::format_config(config()));
+}
+
+fn set(key: &str, value: &str) {
+    if !KEYS.iter().any(|x| x.name == key) {
+        eprintln!("Unknown setting `{key}`. Available settings:");
+        for key in KEYS {
+            eprintln!("  {:<22}{}", key.name, key.help);
+        }
+        process::exit(1);
+    }
+
+    let contents = fs::read_to_string(CONFIG_PATH).unwrap_or_default();
+
+    let result = config::set_value(&contents, key, value)
+        .and_then(|contents| fs::write(CONFIG_PATH, contents).map_err(config::Error::from));
+
+    match result {
+        Ok(()) => println!("Set `{key}` to `{value}` in {CONFIG_PATH}."),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::cli;
+use crate::Day;
+
+/// Runs the solution of a day with `--crosscheck`, which compares the answers of all its variants.
+pub fn handle(day: Day, release: bool) {
+    let day_padded = day.to_string();
+    let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+
+    if release {
+        args.push("--release");
+    }
+
+    args.extend(["--", cli::CROSSCHECK.name]);
+
+    let status = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    if !status.is_ok_and(|x| x.success()) {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,85 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use crate::template::encryption::{self, Key};
+
+/// Decrypts all encrypted inputs next to their encrypted form, e.g. for editors and other tools.
+/// Existing non-empty inputs with different contents are only replaced if `force` is set.
+pub fn handle(force: bool) {
+    let key = match encryption::load_key() {
+        Ok(Some(key)) => key,
+        Ok(None) => {
+            eprintln!("{}", encryption::Error::MissingKey);
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let files = encryption::find_input_files(&format!(".txt.{}", encryption::EXTENSION))
+        .unwrap_or_else(|e| {
+            eprintln!("Could not list inputs: {e}");
+            process::exit(1);
+        });
+
+    let mut decrypted = 0;
+    let mut up_to_date = 0;
+    let mut failed = false;
+
+    for encrypted_path in &files {
+        let path = PathBuf::from(
+            encrypted_path
+                .to_string_lossy()
+                .trim_end_matches(&format!(".{}", encryption::EXTENSION)),
+        );
+
+        match decrypt_input(&key, encrypted_path, &path, force) {
+            Ok(true) => decrypted += 1,
+            Ok(false) => up_to_date += 1,
+            Err(e) => {
+                eprintln!("{e}");
+                failed = true;
+            }
+        }
+    }
+
+    println!("---");
+    println!("🔓 Decrypted {decrypted} input(s), {up_to_date} already up to date.");
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Decrypts an input, returns `false` if the plain text already has the same contents.
+fn decrypt_input(
+    key: &Key,
+    encrypted_path: &Path,
+    path: &Path,
+    force: bool,
+) -> Result<bool, String> {
+    let data = fs::read(encrypted_path).map_err(|e| e.to_string())?;
+    let plaintext = encryption::decrypt(key, encrypted_path, &data).map_err(|e| e.to_string())?;
+
+    if let Ok(existing) = fs::read(path) {
+        if existing == plaintext {
+            return Ok(false);
+        }
+
+        // empty placeholders, e.g. from `scaffold`, are replaced without `--force`.
+        if !force && !existing.is_empty() {
+            return Err(format!(
+                "\"{}\" differs from its encrypted form, use `--force` to overwrite it.",
+                path.display()
+            ));
+        }
+    }
+
+    fs::write(path, plaintext)
+        .map_err(|e| format!("Could not write \"{}\": {e}", path.display()))?;
+    println!("Decrypted \"{}\".", path.display());
+    Ok(true)
+}
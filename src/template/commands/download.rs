@@ -1,15 +1,89 @@
-use crate::template::aoc_cli;
-use crate::Day;
+use std::io::{stdout, Write};
 use std::process;
+use std::thread;
+use std::time::Duration;
+
+use crate::template::aoc_cli;
+use crate::template::config::config;
+use crate::{Clock, Day, SystemClock, Year};
 
-pub fn handle(day: Day) {
+pub fn handle(day: Day, wait: bool) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
+    let clock = SystemClock;
+    // without a configured year, waiting is meant for the advent that comes next, e.g. day 1 on November 30.
+    let year = match config().year() {
+        Some(year) => year,
+        None if wait => Year::upcoming(&clock),
+        None => Year::current(&clock),
+    };
+
+    if let Some(remaining) = day.time_until_unlock(year, &clock) {
+        if !wait {
+            eprintln!(
+                "Day {day} of {year} unlocks in {}. Pass --wait to download it as soon as it unlocks.",
+                format_countdown(remaining)
+            );
+            process::exit(1);
+        }
+
+        wait_for_unlock(day, year, &clock);
+    }
+
+    if let Err(e) = aoc_cli::download(day, year) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
 }
+
+/// Show a countdown until the puzzle unlocks.
+fn wait_for_unlock(day: Day, year: Year, clock: &impl Clock) {
+    let mut stdout = stdout();
+
+    while let Some(remaining) = day.time_until_unlock(year, clock) {
+        print!(
+            "\r⏳ Day {day} of {year} unlocks in {}  ",
+            format_countdown(remaining)
+        );
+        let _ = stdout.flush();
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+
+    println!("\r🎄 Day {day} of {year} is unlocked!{:20}", "");
+}
+
+/// Formats a duration as `HH:MM:SS`, prefixed with the number of days if longer than a day.
+fn format_countdown(duration: Duration) -> String {
+    // round up, so the countdown never shows zero before the puzzle unlocks.
+    let secs = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    let (days, secs) = (secs / 86400, secs % 86400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    );
+
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_countdown;
+    use std::time::Duration;
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_secs(0)), "00:00:00");
+        assert_eq!(format_countdown(Duration::from_millis(200)), "00:00:01");
+        assert_eq!(format_countdown(Duration::from_secs(3723)), "01:02:03");
+        assert_eq!(format_countdown(Duration::from_secs(90000)), "1d 01:00:00");
+    }
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use crate::template::encryption::{self, Key};
+
+/// Encrypts all inputs, including named inputs, and removes the plain text unless `keep` is set.
+pub fn handle(keep: bool, new_key: bool) {
+    if new_key {
+        write_new_key();
+    }
+
+    let key = match encryption::load_key() {
+        Ok(Some(key)) => key,
+        Ok(None) => {
+            eprintln!("{}", encryption::Error::MissingKey);
+            eprintln!("Run `cargo encrypt-inputs --new-key` to create a key.");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let files = encryption::find_input_files(".txt").unwrap_or_else(|e| {
+        eprintln!("Could not list inputs: {e}");
+        process::exit(1);
+    });
+
+    let mut encrypted = 0;
+    let mut up_to_date = 0;
+    let mut failed = false;
+
+    for path in &files {
+        match encrypt_input(&key, path, keep) {
+            Ok(true) => encrypted += 1,
+            Ok(false) => up_to_date += 1,
+            Err(e) => {
+                eprintln!("Could not encrypt \"{}\": {e}", path.display());
+                failed = true;
+            }
+        }
+    }
+
+    println!("---");
+    println!("🔒 Encrypted {encrypted} input(s), {up_to_date} already up to date.");
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Encrypts an input, returns `false` if its encrypted form already has the same contents.
+fn encrypt_input(key: &Key, path: &Path, keep: bool) -> Result<bool, encryption::Error> {
+    let encrypted_path = encryption::get_encrypted_path(path);
+    let plaintext = fs::read(path)?;
+
+    // re-encrypting would change the nonce, and with it the file, on every run.
+    // an empty file is a placeholder, e.g. from `scaffold`, and must not replace the encrypted input.
+    let up_to_date = (plaintext.is_empty() && encrypted_path.exists())
+        || fs::read(&encrypted_path)
+            .ok()
+            .and_then(|data| encryption::decrypt(key, &encrypted_path, &data).ok())
+            .is_some_and(|x| x == plaintext);
+
+    if !up_to_date {
+        encryption::encrypt_file(key, path)?;
+        println!("Encrypted \"{}\".", encrypted_path.display());
+    }
+
+    if !keep {
+        fs::remove_file(path)?;
+    }
+
+    Ok(!up_to_date)
+}
+
+fn write_new_key() {
+    let path = encryption::get_key_path();
+
+    if path.exists() {
+        eprintln!(
+            "\"{}\" already exists, remove it to create a new key.",
+            path.display()
+        );
+        process::exit(1);
+    }
+
+    let key = Key::generate();
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&path, format!("{}\n", key.to_hex())));
+
+    if let Err(e) = result {
+        eprintln!("Could not write \"{}\": {e}", path.display());
+        process::exit(1);
+    }
+
+    println!("🔑 Wrote a new input key to \"{}\".", path.display());
+    println!("🔑 Share it with your team, but do not commit it.");
+}
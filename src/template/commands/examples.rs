@@ -0,0 +1,65 @@
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+
+use crate::template::examples::get_manifest_path;
+use crate::template::{cli, readme_benchmarks::get_path_for_bin, ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, Day};
+
+/// Check the example manifests of a single day or of all days and print a pass/fail matrix per day.
+pub fn handle(day: Option<Day>) {
+    let days: Vec<Day> = match day {
+        Some(day) => vec![day],
+        None => all_days()
+            .filter(|day| {
+                Path::new(&get_path_for_bin(*day)).exists() && get_manifest_path(*day).exists()
+            })
+            .collect(),
+    };
+
+    let mut failed: Vec<Day> = vec![];
+
+    for (i, day) in days.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let day_padded = day.to_string();
+        let status = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--bin",
+                &day_padded,
+                "--",
+                cli::EXAMPLES.name,
+            ])
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status();
+
+        if !status.is_ok_and(|x| x.success()) {
+            failed.push(*day);
+        }
+    }
+
+    println!();
+
+    if failed.is_empty() {
+        println!(
+            "{ANSI_BOLD}Examples:{ANSI_RESET} ✔ {} day(s) passed",
+            days.len()
+        );
+    } else {
+        let failed_str: Vec<String> = failed.iter().map(ToString::to_string).collect();
+        println!(
+            "{ANSI_BOLD}Examples:{ANSI_RESET} ✖ {} of {} day(s) failed: {}",
+            failed.len(),
+            days.len(),
+            failed_str.join(", ")
+        );
+        process::exit(1);
+    }
+}
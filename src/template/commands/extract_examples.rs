@@ -0,0 +1,174 @@
+use std::fs;
+use std::process;
+
+use crate::template::config::config;
+use crate::template::examples::{self, Example};
+use crate::template::puzzle::{self, CodeBlock};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, Part};
+
+/// Number of lines shown per candidate code block.
+const PREVIEW_LINES: usize = 4;
+
+pub fn handle(day: Day, select: Option<Vec<usize>>, force: bool) {
+    let puzzle_path = puzzle::get_puzzle_path(day);
+
+    let Ok(description) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Could not read \"{}\". Run `cargo download {day}` first.",
+            puzzle_path.display()
+        );
+        process::exit(1);
+    };
+
+    let blocks = puzzle::extract_code_blocks(&description);
+    let (answer_one, answer_two) = puzzle::extract_answers(&description);
+
+    print_candidates(&blocks);
+    println!(
+        "{ANSI_BOLD}Proposed answers:{ANSI_RESET} part one {}, part two {}",
+        answer_one.as_deref().unwrap_or("-"),
+        answer_two.as_deref().unwrap_or("- (not unlocked yet?)")
+    );
+
+    let Some(select) = select else {
+        println!("---");
+        println!("🎄 Type `cargo extract-examples {day} --select <n>[,<n>...]` to write examples.");
+        return;
+    };
+
+    let selected: Vec<&CodeBlock> = select
+        .iter()
+        .map(|i| {
+            i.checked_sub(1)
+                .and_then(|i| blocks.get(i))
+                .unwrap_or_else(|| {
+                    eprintln!("No candidate with number {i}.");
+                    process::exit(1);
+                })
+        })
+        .collect();
+
+    let files: Vec<String> = if selected.len() == 1 {
+        vec![format!("{day}.txt")]
+    } else {
+        (1..=selected.len())
+            .map(|i| format!("{day}-{i}.txt"))
+            .collect()
+    };
+
+    println!("---");
+
+    // files that were skipped keep their contents, and with them their answers.
+    let written: Vec<bool> = files
+        .iter()
+        .zip(&selected)
+        .map(|(file, block)| write_example(file, &block.content, force))
+        .collect();
+
+    if !written.contains(&true) {
+        return;
+    }
+
+    // part one's answer belongs to its first selected example, if any.
+    // part two reuses that example unless one of the selected blocks is from part two.
+    let file_one = selected.iter().position(|x| x.part == Part::One);
+    let file_two = selected
+        .iter()
+        .position(|x| x.part == Part::Two)
+        .or(file_one);
+    let is_written = |i: Option<usize>| i.filter(|&i| written[i]).map(|i| &files[i]);
+    let (file_one, file_two) = (is_written(file_one), is_written(file_two));
+
+    let mut manifest = match examples::read_manifest(day) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    for file in files.iter().zip(&written).filter(|x| *x.1).map(|x| x.0) {
+        if !manifest.iter().any(|x| &x.file == file) {
+            manifest.push(Example {
+                file: file.clone(),
+                part_one: None,
+                part_two: None,
+            });
+        }
+    }
+
+    for example in &mut manifest {
+        if Some(&example.file) == file_one && (example.part_one.is_none() || force) {
+            example.part_one.clone_from(&answer_one);
+        }
+        if Some(&example.file) == file_two && (example.part_two.is_none() || force) {
+            example.part_two.clone_from(&answer_two);
+        }
+    }
+
+    match examples::write_manifest(day, &manifest) {
+        Ok(()) => println!(
+            "Updated example manifest \"{}\"",
+            examples::get_manifest_path(day).display()
+        ),
+        Err(e) => {
+            eprintln!("Failed to write example manifest: {e}");
+            process::exit(1);
+        }
+    }
+
+    println!(
+        "🎄 Check the proposed answers, then type `cargo test --bin {day}` to run the examples."
+    );
+}
+
+fn print_candidates(blocks: &[CodeBlock]) {
+    if blocks.is_empty() {
+        println!("No code blocks found in puzzle description.");
+        return;
+    }
+
+    for (i, block) in blocks.iter().enumerate() {
+        let lines: Vec<&str> = block.content.lines().collect();
+
+        println!(
+            "{ANSI_BOLD}[{}]{ANSI_RESET} {ANSI_ITALIC}part {}, {} line(s){ANSI_RESET}",
+            i + 1,
+            block.part,
+            lines.len()
+        );
+
+        for line in lines.iter().take(PREVIEW_LINES) {
+            println!("    {line}");
+        }
+
+        if lines.len() > PREVIEW_LINES {
+            println!("    ...");
+        }
+    }
+}
+
+/// Writes an example file, returns `false` if it was skipped.
+fn write_example(file: &str, content: &str, force: bool) -> bool {
+    let path = config().examples_dir().join(file);
+    let path_str = path.display();
+
+    // empty files are created by `scaffold` and can be overwritten safely.
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    if !existing.is_empty() && existing != content && !force {
+        println!("Skipped \"{path_str}\": file exists, pass --force to overwrite.");
+        return false;
+    }
+
+    match fs::write(&path, content) {
+        Ok(()) => {
+            println!("Wrote example file \"{path_str}\"");
+            true
+        }
+        Err(e) => {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use std::process;
+
+use crate::template::config::config;
+use crate::template::{encryption, inspect};
+use crate::Day;
+
+/// Prints statistics about the input of a day, see [`inspect`].
+pub fn handle(day: Day) {
+    let input_path = config().inputs_dir().join(format!("{day}.txt"));
+
+    let input = match encryption::read_input_file(&input_path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Could not read \"{}\": {e}", input_path.display());
+            eprintln!("Run `cargo download {day}` first.");
+            process::exit(1);
+        }
+    };
+
+    print!("{}", inspect::format_report(&inspect::inspect(&input)));
+}
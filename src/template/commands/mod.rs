@@ -1,5 +1,16 @@
 pub mod all;
+pub mod bench_compare;
+pub mod completions;
+pub mod config;
+pub mod crosscheck;
+pub mod decrypt_inputs;
 pub mod download;
+pub mod encrypt_inputs;
+pub mod examples;
+pub mod extract_examples;
+pub mod inspect;
 pub mod read;
+pub mod readme;
 pub mod scaffold;
 pub mod solve;
+pub mod test_day;
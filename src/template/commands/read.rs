@@ -1,9 +1,34 @@
-use std::process;
+use std::fs;
+use std::io::{stdout, IsTerminal, Write};
+use std::process::{self, Command, Stdio};
 
-use crate::template::aoc_cli;
+use crate::template::{aoc_cli, puzzle, render};
 use crate::Day;
 
-pub fn handle(day: Day) {
+/// Descriptions longer than this are shown in a pager when stdout is a terminal.
+const DEFAULT_PAGE_HEIGHT: usize = 40;
+
+pub fn handle(day: Day, refresh: bool) {
+    let puzzle_path = puzzle::get_puzzle_path(day);
+
+    if refresh || !puzzle_path.exists() {
+        fetch(day);
+        return;
+    }
+
+    let description = match fs::read_to_string(&puzzle_path) {
+        Ok(description) => description,
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {e}", puzzle_path.display());
+            process::exit(1);
+        }
+    };
+
+    show(&render::render_markdown(&description));
+}
+
+/// Fetch the description via aoc-cli, which prints it and saves it to `data/puzzles`.
+fn fetch(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
@@ -14,3 +39,41 @@ pub fn handle(day: Day) {
         process::exit(1);
     };
 }
+
+fn page_height() -> usize {
+    std::env::var("LINES")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(DEFAULT_PAGE_HEIGHT)
+}
+
+/// Print text, using `$PAGER` (or `less -R`) if it does not fit on the screen.
+fn show(text: &str) {
+    if !stdout().is_terminal() || text.lines().count() <= page_height() {
+        println!("{text}");
+        return;
+    }
+
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -R".into());
+    let mut pager_args = pager.split_whitespace();
+
+    let spawned = pager_args.next().and_then(|cmd| {
+        Command::new(cmd)
+            .args(pager_args)
+            .stdin(Stdio::piped())
+            .spawn()
+            .ok()
+    });
+
+    let Some(mut child) = spawned else {
+        println!("{text}");
+        return;
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // the pager may be closed before all text was written, ignore the broken pipe.
+        let _ = writeln!(stdin, "{text}");
+    }
+
+    let _ = child.wait();
+}
@@ -0,0 +1,75 @@
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use crate::template::answers;
+use crate::template::cli;
+use crate::template::config::config;
+use crate::template::readme_stars::{self, Stars};
+use crate::{SystemClock, Year};
+
+pub fn handle(leaderboard: Option<&Path>, member: Option<&str>) {
+    let (year, mut stars) = match leaderboard {
+        Some(path) => from_leaderboard(path, member),
+        None => from_answers(),
+    };
+
+    let count: usize = stars
+        .iter()
+        .map(|x| usize::from(x.part_one) + usize::from(x.part_two))
+        .sum();
+
+    // an empty table would replace the one in the readme, e.g. before any answer was recorded.
+    if count == 0 {
+        eprintln!("No stars of {year} found, the readme was left unchanged.");
+        if leaderboard.is_none() {
+            eprintln!(
+                "Answers are recorded by `cargo solve <day> {} <part>`, or pass a private leaderboard with `{} <file>`.",
+                cli::SUBMIT.name,
+                cli::LEADERBOARD.name
+            );
+        }
+        process::exit(1);
+    }
+
+    readme_stars::add_titles(&mut stars);
+
+    match readme_stars::update(year, &stars) {
+        Ok(()) => println!("Successfully updated README with {count} star(s) of {year}."),
+        Err(e) => {
+            eprintln!("Failed to update readme with stars: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn from_answers() -> (Year, Vec<Stars>) {
+    let year = config().year_or_current(&SystemClock);
+
+    match answers::read_answers() {
+        Ok(answers) => (year, readme_stars::stars_from_answers(&answers, year)),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn from_leaderboard(path: &Path, member: Option<&str>) -> (Year, Vec<Stars>) {
+    let json = fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("Failed to read \"{}\": {e}", path.display());
+        process::exit(1);
+    });
+
+    match readme_stars::stars_from_leaderboard(&json, member) {
+        // the leaderboard belongs to one event, which takes precedence over the configured year.
+        Ok((year, stars)) => (
+            year.unwrap_or_else(|| config().year_or_current(&SystemClock)),
+            stars,
+        ),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
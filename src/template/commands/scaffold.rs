@@ -1,92 +1,242 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
     process,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use crate::Day;
+use crate::template::config::config;
+use crate::template::examples::{self, Example};
+use crate::template::templates::{self, Context, Template, DEFAULT_RETURN_TYPE};
+use crate::template::{aoc_cli, encryption, puzzle};
+use crate::{Day, SystemClock};
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+/// Folder that keeps the modules replaced by `scaffold --force`.
+const BACKUP_DIR: &str = ".aoc/backups";
 
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+pub struct Options<'a> {
+    pub template: Option<&'a str>,
+    pub types: Option<&'a [String]>,
+    /// Replace an existing module, keeping a backup of it.
+    pub force: bool,
+    /// Number of numbered example files (`DD-1.txt`, `DD-2.txt`, ...) to create.
+    pub examples: Option<usize>,
+    pub download: bool,
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+/// What scaffold did to one of the files of a day.
+enum Status {
+    Created,
+    Updated,
+    Exists,
+    Replaced { backup: PathBuf },
+    Downloaded,
+    Skipped(String),
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn print_status(status: &Status, artifact: &str, path: &Path) {
+    let path = path.display();
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+    match status {
+        Status::Created => println!("created    {artifact} \"{path}\""),
+        Status::Updated => println!("updated    {artifact} \"{path}\""),
+        Status::Exists => println!("exists     {artifact} \"{path}\""),
+        Status::Replaced { backup } => println!(
+            "replaced   {artifact} \"{path}\" (backup: \"{}\")",
+            backup.display()
+        ),
+        Status::Downloaded => println!("downloaded {artifact} \"{path}\""),
+        Status::Skipped(reason) => println!("skipped    {artifact} \"{path}\" ({reason})"),
     }
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+fn safe_create_file(path: &Path) -> Result<File, std::io::Error> {
+    OpenOptions::new().write(true).create_new(true).open(path)
+}
+
+/// Creates an empty file, unless it exists already.
+fn create_empty_file(path: &Path) -> Result<Status, io::Error> {
+    match safe_create_file(path) {
+        Ok(_) => Ok(Status::Created),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(Status::Exists),
+        Err(e) => Err(e),
     }
 }
-"#;
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+fn unwrap_or_exit<T, E: std::fmt::Display>(result: Result<T, E>, message: &str) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("{message}: {e}");
+        process::exit(1);
+    })
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Fetches input and puzzle description, unless the input was already downloaded.
+fn download(day: Day, input_path: &Path, force: bool) -> Status {
+    let has_input = fs::metadata(input_path).is_ok_and(|x| x.len() > 0)
+        || encryption::get_encrypted_path(input_path).exists();
+
+    if has_input && !force {
+        return Status::Exists;
+    }
+
+    if aoc_cli::check().is_err() {
+        return Status::Skipped("aoc-cli is not installed".into());
+    }
+
+    match aoc_cli::download(day, config().year_or_current(&SystemClock)) {
+        Ok(_) => Status::Downloaded,
+        Err(e) => Status::Skipped(e.to_string()),
+    }
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Writes the module, moving an existing module to the backup folder if `force` is set.
+fn write_module(path: &Path, module: &str, day: Day, force: bool) -> Result<Status, io::Error> {
+    let status = match safe_create_file(path) {
+        Ok(mut file) => {
+            file.write_all(module.as_bytes())?;
+            return Ok(Status::Created);
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists && !force => return Ok(Status::Exists),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |x| x.as_secs());
+            let backup = Path::new(BACKUP_DIR).join(format!("{day}-{timestamp}.rs"));
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+            fs::create_dir_all(BACKUP_DIR)?;
+            fs::copy(path, &backup)?;
+            Status::Replaced { backup }
         }
+        Err(e) => return Err(e),
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+    fs::write(path, module)?;
+    Ok(status)
+}
+
+/// Creates the example manifest from the template, or adds the numbered examples to an existing one.
+fn write_manifest(
+    day: Day,
+    path: &Path,
+    manifest: &str,
+    numbered: &[Example],
+) -> Result<Status, examples::Error> {
+    if !path.exists() {
+        let contents = if numbered.is_empty() {
+            manifest.to_string()
+        } else {
+            examples::format_manifest(numbered)
+        };
+        fs::write(path, contents)?;
+        return Ok(Status::Created);
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    let mut existing = examples::read_manifest(day)?;
+    let missing: Vec<Example> = numbered
+        .iter()
+        .filter(|x| !existing.iter().any(|y| y.file == x.file))
+        .cloned()
+        .collect();
+
+    if missing.is_empty() {
+        return Ok(Status::Exists);
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    existing.extend(missing);
+    examples::write_manifest(day, &existing)?;
+    Ok(Status::Updated)
+}
+
+pub fn handle(day: Day, options: &Options) {
+    let input_path = config().inputs_dir().join(format!("{day}.txt"));
+    let puzzle_path = puzzle::get_puzzle_path(day);
+    let manifest_path = examples::get_manifest_path(day);
+    let module_path = PathBuf::from(format!("src/bin/{day}.rs"));
+
+    let template = unwrap_or_exit(
+        Template::load(options.template.or(config().template.as_deref())),
+        "Failed to load template",
+    );
+
+    // download first, so the title of the puzzle is available to the template.
+    if options.download {
+        let status = download(day, &input_path, options.force);
+        print_status(&status, "input & puzzle", &input_path);
+    }
+
+    let part_one_type = options
+        .types
+        .and_then(|x| x.first())
+        .map_or(DEFAULT_RETURN_TYPE, String::as_str);
+    let part_two_type = options
+        .types
+        .and_then(|x| x.get(1))
+        .map_or(part_one_type, String::as_str);
+
+    let context = Context {
+        day,
+        year: config().year_or_current(&SystemClock),
+        title: fs::read_to_string(&puzzle_path)
+            .ok()
+            .and_then(|x| puzzle::extract_title(&x)),
+        part_one_type: part_one_type.into(),
+        part_two_type: part_two_type.into(),
+    };
+
+    let module = unwrap_or_exit(
+        templates::render(&template.module, &context),
+        "Failed to render module template",
+    );
+    let manifest = unwrap_or_exit(
+        templates::render(&template.manifest, &context),
+        "Failed to render manifest template",
+    );
+
+    let status = unwrap_or_exit(
+        write_module(&module_path, &module, day, options.force),
+        "Failed to write module file",
+    );
+    print_status(&status, "module file", &module_path);
+
+    // an empty input next to the encrypted one would only be a placeholder.
+    let status = if encryption::exists(&input_path) {
+        Status::Exists
+    } else {
+        unwrap_or_exit(
+            create_empty_file(&input_path),
+            "Failed to create input file",
+        )
+    };
+    print_status(&status, "input file", &input_path);
+
+    let numbered: Vec<Example> = (1..=options.examples.unwrap_or(0))
+        .map(|i| Example {
+            file: format!("{day}-{i}.txt"),
+            part_one: None,
+            part_two: None,
+        })
+        .collect();
+
+    let status = unwrap_or_exit(
+        write_manifest(day, &manifest_path, &manifest, &numbered),
+        "Failed to write example manifest",
+    );
+    print_status(&status, "example manifest", &manifest_path);
+
+    // create the example files listed in the manifest, which may differ per template.
+    let examples = unwrap_or_exit(
+        examples::read_manifest(day),
+        "Failed to read example manifest",
+    );
+
+    for example in examples {
+        let example_path = config().examples_dir().join(&example.file);
+        let status = unwrap_or_exit(
+            create_empty_file(&example_path),
+            "Failed to create example file",
+        );
+        print_status(&status, "example file", &example_path);
     }
 
     println!("---");
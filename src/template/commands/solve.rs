@@ -1,23 +1,93 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::Day;
+use crate::template::{cli, commands::test_day, watch};
+use crate::{Day, Part};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
+/// Flags that are passed on to the solution binary.
+pub struct Options {
+    pub release: bool,
+    pub time: bool,
+    /// Benchmark with reduced noise, implies `time`.
+    pub stable: bool,
+    pub allocs: bool,
+    /// Sample every step and write flamegraphs.
+    pub profile: bool,
+    /// Named variant of the parts to run.
+    pub variant: Option<String>,
+    /// Run every named input instead of the input of the day.
+    pub all_inputs: bool,
+    pub timeout: Option<u64>,
+    pub submit: Option<Part>,
+}
+
+pub fn handle(day: Day, options: &Options, watch: bool) {
+    if !watch {
+        run_solution(day, options, options.submit);
+        return;
+    }
+
+    if options.submit.is_some() {
+        eprintln!("--submit can not be combined with --watch.");
+        process::exit(1);
+    }
+
+    watch::watch_day(day, || {
+        if test_day::run_tests(day, options.release) {
+            println!();
+            run_solution(day, options, None);
+        }
+    });
+}
+
+fn run_solution(day: Day, options: &Options, submit_part: Option<Part>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
-    if release {
+    if options.release {
         cmd_args.push("--release".to_string());
     }
 
+    let features: Vec<&str> = [
+        (options.allocs, "alloc_tracking"),
+        (options.profile, "profile"),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, feature)| enabled.then_some(feature))
+    .collect();
+
+    if !features.is_empty() {
+        cmd_args.push("--features".to_string());
+        cmd_args.push(features.join(","));
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
+        cmd_args.push(cli::SUBMIT.name.to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    if time {
-        cmd_args.push("--time".to_string());
+    if options.profile {
+        cmd_args.push(cli::PROFILE.name.to_string());
+    }
+
+    if let Some(variant) = &options.variant {
+        cmd_args.push(cli::VARIANT.name.to_string());
+        cmd_args.push(variant.clone());
+    }
+
+    if options.all_inputs {
+        cmd_args.push(cli::ALL_INPUTS.name.to_string());
+    }
+
+    if options.stable {
+        cmd_args.push(cli::STABLE.name.to_string());
+    } else if options.time {
+        cmd_args.push(cli::TIME.name.to_string());
+    }
+
+    if let Some(timeout) = options.timeout {
+        cmd_args.push(cli::TIMEOUT.name.to_string());
+        cmd_args.push(timeout.to_string());
     }
 
     let mut cmd = Command::new("cargo")
@@ -0,0 +1,70 @@
+use std::process::{self, Command};
+
+use crate::template::{watch, ANSI_BOLD, ANSI_RESET};
+use crate::Day;
+
+pub fn handle(day: Day, release: bool, watch: bool) {
+    if watch {
+        watch::watch_day(day, || {
+            run_tests(day, release);
+        });
+    }
+
+    if !run_tests(day, release) {
+        process::exit(1);
+    }
+}
+
+/// Run the tests of a day's module and print a summary.
+/// The full test output is only shown if a test failed.
+pub fn run_tests(day: Day, release: bool) -> bool {
+    let mut cmd_args = vec!["test", "--quiet", "--bin"];
+    let day_padded = day.to_string();
+    cmd_args.push(&day_padded);
+
+    if release {
+        cmd_args.push("--release");
+    }
+
+    let output = match Command::new("cargo").args(&cmd_args).output() {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run tests: {e}");
+            return false;
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let summary = stdout
+        .lines()
+        .find(|l| l.starts_with("test result:"))
+        .map(summarize);
+
+    if output.status.success() {
+        println!(
+            "{ANSI_BOLD}Tests:{ANSI_RESET} ✔ {}",
+            summary.as_deref().unwrap_or("ok")
+        );
+    } else {
+        // compile errors end up on stderr, test failures on stdout.
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        print!("{stdout}");
+        println!(
+            "{ANSI_BOLD}Tests:{ANSI_RESET} ✖ {}",
+            summary.as_deref().unwrap_or("failed to compile")
+        );
+    }
+
+    output.status.success()
+}
+
+/// Shortens a libtest summary like `test result: ok. 2 passed; 0 failed; 0 ignored; ...` to `2 passed, 0 failed`.
+fn summarize(line: &str) -> String {
+    line.split(". ")
+        .nth(1)
+        .unwrap_or(line)
+        .split("; ")
+        .take(2)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
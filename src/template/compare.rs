@@ -0,0 +1,280 @@
+/// Compares the timings of two revisions of the solutions, see the `bench-compare` command.
+///
+/// A change counts if it is both statistically significant (Welch's t-test on the mean, the exact
+/// mean and standard deviation of every step are printed with `--time`) and larger than a relative threshold,
+/// so that tiny but consistent differences do not fail a comparison.
+use std::fmt::Display;
+use std::time::Duration;
+
+use crate::template::readme_benchmarks::{Measurement, Timing, Timings};
+use crate::template::table;
+use crate::Day;
+
+/// Critical value of a two-sided t-test at 95% confidence, for the sample sizes of `--time`.
+const CRITICAL_T: f64 = 1.96;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Faster,
+    Slower,
+    Unchanged,
+    /// The step only ran in one of the revisions.
+    Missing,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Verdict::Faster => "faster",
+            Verdict::Slower => "slower",
+            Verdict::Unchanged => "~",
+            Verdict::Missing => "missing",
+        };
+
+        f.write_str(s)
+    }
+}
+
+/// The comparison of one step of a day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub step: &'static str,
+    pub old: Option<Timing>,
+    pub new: Option<Timing>,
+    /// The t-statistic of the difference, if both steps have a standard deviation.
+    pub t: Option<f64>,
+    pub verdict: Verdict,
+}
+
+impl Comparison {
+    /// New mean time relative to the old one, below 1 is faster.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn ratio(&self) -> Option<f64> {
+        let old = self.old.as_ref()?.measurement()?;
+        let new = self.new.as_ref()?.measurement()?;
+        Some(new.mean.as_nanos() as f64 / old.mean.as_nanos().max(1) as f64)
+    }
+}
+
+/// Welch's t-statistic of the difference of two means. `None` if a deviation is unknown.
+#[allow(clippy::cast_precision_loss)]
+fn welch_t(old: &Measurement, new: &Measurement) -> Option<f64> {
+    let variance = |x: &Measurement| {
+        let stddev = x.stddev?.as_nanos() as f64;
+        (x.samples > 1).then(|| stddev * stddev / x.samples as f64)
+    };
+
+    let standard_error = (variance(old)? + variance(new)?).sqrt();
+    let difference = new.mean.as_nanos() as f64 - old.mean.as_nanos() as f64;
+
+    Some(if standard_error == 0.0 {
+        // both steps took exactly the same time in every sample.
+        match difference {
+            d if d > 0.0 => f64::INFINITY,
+            d if d < 0.0 => f64::NEG_INFINITY,
+            _ => 0.0,
+        }
+    } else {
+        difference / standard_error
+    })
+}
+
+/// Compares every step of a day that ran in either revision.
+/// `threshold` is the smallest relative change that counts, e.g. `0.05` for 5%.
+#[must_use]
+pub fn compare(old: &Timings, new: &Timings, threshold: f64) -> Vec<Comparison> {
+    let day = new.day;
+    let steps = [
+        ("Parse", &old.parse, &new.parse),
+        ("Part 1", &old.part_1, &new.part_1),
+        ("Part 2", &old.part_2, &new.part_2),
+    ];
+
+    steps
+        .into_iter()
+        .filter(|(_, old, new)| old.is_some() || new.is_some())
+        .map(|(step, old, new)| {
+            let mut comparison = Comparison {
+                day,
+                step,
+                old: old.clone(),
+                new: new.clone(),
+                t: None,
+                verdict: Verdict::Unchanged,
+            };
+
+            comparison.verdict = match (old, new) {
+                (Some(Timing::Measured(a)), Some(Timing::Measured(b))) => {
+                    comparison.t = welch_t(a, b);
+                    // without deviations, only the threshold decides.
+                    let is_significant = comparison.t.is_none_or(|t| t.abs() > CRITICAL_T);
+
+                    match comparison.ratio() {
+                        Some(ratio) if is_significant && ratio > 1.0 + threshold => Verdict::Slower,
+                        Some(ratio) if is_significant && ratio < 1.0 - threshold => Verdict::Faster,
+                        _ => Verdict::Unchanged,
+                    }
+                }
+                (Some(Timing::Measured(_)), Some(Timing::TimedOut(_))) => Verdict::Slower,
+                (Some(Timing::TimedOut(_)), Some(Timing::Measured(_))) => Verdict::Faster,
+                (Some(Timing::TimedOut(_)), Some(Timing::TimedOut(_))) => Verdict::Unchanged,
+                _ => Verdict::Missing,
+            };
+
+            comparison
+        })
+        .collect()
+}
+
+fn format_timing(timing: Option<&Timing>) -> String {
+    match timing {
+        Some(Timing::Measured(x)) => match x.stddev {
+            Some(stddev) => format!("{:.1?} ± {stddev:.1?}", x.mean),
+            None => format!("{:.1?}", x.mean),
+        },
+        Some(Timing::TimedOut(timeout)) => format!("TIMEOUT ({timeout:?})"),
+        None => "-".into(),
+    }
+}
+
+/// Formats the comparisons as an aligned table with one row per step.
+#[must_use]
+pub fn format_table(comparisons: &[Comparison]) -> String {
+    let header = ["Day", "Step", "Old", "New", "Ratio", "t", "Verdict"];
+
+    let rows: Vec<[String; 7]> = comparisons
+        .iter()
+        .map(|x| {
+            [
+                x.day.to_string(),
+                x.step.to_string(),
+                format_timing(x.old.as_ref()),
+                format_timing(x.new.as_ref()),
+                x.ratio().map_or_else(|| "-".into(), |x| format!("{x:.2}x")),
+                x.t.map_or_else(|| "-".into(), |x| format!("{x:.1}")),
+                x.verdict.to_string(),
+            ]
+        })
+        .collect();
+
+    table::format_columns(header, &rows, "  ") + "\n"
+}
+
+/// The total mean time of both revisions, only counting steps that were measured in both.
+#[must_use]
+pub fn totals(comparisons: &[Comparison]) -> (Duration, Duration) {
+    comparisons
+        .iter()
+        .filter_map(|x| {
+            let old = x.old.as_ref()?.measurement()?;
+            let new = x.new.as_ref()?.measurement()?;
+            Some((old.mean, new.mean))
+        })
+        .fold((Duration::ZERO, Duration::ZERO), |acc, x| {
+            (acc.0 + x.0, acc.1 + x.1)
+        })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, format_table, totals, Verdict};
+    use crate::day;
+    use crate::template::readme_benchmarks::{parse_record, Measurement, Timing, Timings};
+    use std::time::Duration;
+
+    fn measured(mean_us: u64, stddev_us: u64) -> Option<Timing> {
+        Some(Timing::Measured(Measurement {
+            mean: Duration::from_micros(mean_us),
+            median: Some(Duration::from_micros(mean_us)),
+            stddev: Some(Duration::from_micros(stddev_us)),
+            samples: 100,
+            allocs: None,
+        }))
+    }
+
+    fn timings(part_1: Option<Timing>, part_2: Option<Timing>) -> Timings {
+        Timings {
+            day: day!(1),
+            parse: None,
+            part_1,
+            part_2,
+        }
+    }
+
+    #[test]
+    fn detects_significant_changes() {
+        let old = timings(measured(100, 2), measured(100, 2));
+        let new = timings(measured(150, 2), measured(50, 2));
+        let comparisons = compare(&old, &new, 0.05);
+
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].verdict, Verdict::Slower);
+        assert_eq!(comparisons[0].ratio(), Some(1.5));
+        assert_eq!(comparisons[1].verdict, Verdict::Faster);
+    }
+
+    #[test]
+    fn ignores_noise_and_small_changes() {
+        let old = timings(measured(100, 100), measured(100, 1));
+        // large change, but within the noise.
+        let new = timings(measured(120, 100), measured(102, 1));
+        let comparisons = compare(&old, &new, 0.05);
+
+        assert_eq!(comparisons[0].verdict, Verdict::Unchanged);
+        // significant, but below the threshold.
+        assert!(comparisons[1].t.unwrap() > 1.96);
+        assert_eq!(comparisons[1].verdict, Verdict::Unchanged);
+    }
+
+    #[test]
+    fn compares_exact_timings() {
+        // both means display as 1.2ms, or 1.2ms and 1.3ms, when rounded to one decimal.
+        let record = |line: &str| Some(Timing::Measured(parse_record(line).unwrap().1));
+        let old = timings(
+            record("@timing Part 1: mean_ns=1249000 median_ns=1248000 stddev_ns=40000 samples=800"),
+            None,
+        );
+        let new = timings(
+            record("@timing Part 1: mean_ns=1251000 median_ns=1250000 stddev_ns=40000 samples=800"),
+            None,
+        );
+        let comparisons = compare(&old, &new, 0.05);
+
+        assert!(comparisons[0].t.unwrap() < 1.96);
+        assert!(comparisons[0].ratio().unwrap() < 1.01);
+        assert_eq!(comparisons[0].verdict, Verdict::Unchanged);
+    }
+
+    #[test]
+    fn compares_timeouts_and_missing_steps() {
+        let old = timings(measured(100, 1), None);
+        let new = timings(
+            Some(Timing::TimedOut(Duration::from_secs(5))),
+            measured(10, 1),
+        );
+        let comparisons = compare(&old, &new, 0.05);
+
+        assert_eq!(comparisons[0].verdict, Verdict::Slower);
+        assert_eq!(comparisons[1].verdict, Verdict::Missing);
+        assert_eq!(totals(&comparisons), (Duration::ZERO, Duration::ZERO));
+    }
+
+    #[test]
+    fn formats_table() {
+        let old = timings(measured(100, 2), None);
+        let new = timings(measured(50, 2), measured(10, 1));
+        let table = format_table(&compare(&old, &new, 0.05));
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                "Day  Step    Old              New             Ratio  t       Verdict",
+                "01   Part 1  100.0µs ± 2.0µs  50.0µs ± 2.0µs  0.50x  -176.8  faster",
+                "01   Part 2  -                10.0µs ± 1.0µs  -      -       missing",
+            ]
+        );
+    }
+}
@@ -0,0 +1,652 @@
+/// Project configuration: an optional `aoc.toml` in the project root, loaded once per process.
+///
+/// ```toml
+/// year = 2023
+/// template = "grid"
+/// session_file = "~/.adventofcode.session"
+/// key_file = "~/.aoc-input.key"
+///
+/// [paths]
+/// data = "data"
+/// readme = "README.md"
+///
+/// [bench]
+/// budget_ms = 1000
+/// min_samples = 10
+/// max_samples = 10000
+/// core = 3
+///
+/// [table]
+/// columns = "time,allocs,share"
+/// sort = "slowest"
+/// highlight = 3
+/// output = "benchmarks.md"
+///
+/// [defaults]
+/// release = true
+/// timeout = 10
+/// ```
+///
+/// Every setting is optional. The `AOC_YEAR` environment variable takes precedence over `year`.
+use std::fmt::{Display, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{fs, io, process};
+
+use crate::template::readme_benchmarks::{Column, Sort};
+use crate::{Clock, Year};
+
+pub const CONFIG_PATH: &str = "aoc.toml";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "invalid {CONFIG_PATH}: {e}"),
+            Error::IO(e) => write!(f, "could not access {CONFIG_PATH}: {e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Integer,
+    Bool,
+    String,
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Kind::Integer => write!(f, "an integer"),
+            Kind::Bool => write!(f, "true or false"),
+            Kind::String => write!(f, "a string"),
+        }
+    }
+}
+
+/// A setting of the configuration file, e.g. `bench.budget_ms`.
+pub struct Key {
+    pub name: &'static str,
+    kind: Kind,
+    pub help: &'static str,
+}
+
+pub const KEYS: &[Key] = &[
+    Key {
+        name: "year",
+        kind: Kind::Integer,
+        help: "Year of the puzzles, the current year by default",
+    },
+    Key {
+        name: "template",
+        kind: Kind::String,
+        help: "Template used by `scaffold` if no `--template` is passed",
+    },
+    Key {
+        name: "session_file",
+        kind: Kind::String,
+        help: "Session cookie file passed to aoc-cli",
+    },
+    Key {
+        name: "key_file",
+        kind: Kind::String,
+        help: "Key file for encrypted inputs, `.aoc/input.key` by default",
+    },
+    Key {
+        name: "paths.data",
+        kind: Kind::String,
+        help: "Folder of inputs, examples and puzzle descriptions",
+    },
+    Key {
+        name: "paths.readme",
+        kind: Kind::String,
+        help: "Readme that holds the benchmark table",
+    },
+    Key {
+        name: "bench.budget_ms",
+        kind: Kind::Integer,
+        help: "Approximate time spent benchmarking each part",
+    },
+    Key {
+        name: "bench.min_samples",
+        kind: Kind::Integer,
+        help: "Minimum number of benchmark samples",
+    },
+    Key {
+        name: "bench.max_samples",
+        kind: Kind::Integer,
+        help: "Maximum number of benchmark samples",
+    },
+    Key {
+        name: "bench.core",
+        kind: Kind::Integer,
+        help: "Core that `--stable` pins benchmarks to, the last core by default",
+    },
+    Key {
+        name: "table.columns",
+        kind: Kind::String,
+        help:
+            "Columns of the benchmark table: time, median, stddev, samples, allocs, speedup, share",
+    },
+    Key {
+        name: "table.sort",
+        kind: Kind::String,
+        help: "Order of the benchmark table: day, slowest or fastest",
+    },
+    Key {
+        name: "table.highlight",
+        kind: Kind::Integer,
+        help: "Number of slowest parts that are highlighted in the benchmark table",
+    },
+    Key {
+        name: "table.output",
+        kind: Kind::String,
+        help: "Markdown or HTML file for the benchmark table, instead of the readme",
+    },
+    Key {
+        name: "defaults.release",
+        kind: Kind::Bool,
+        help: "Pass `--release` to `solve`, `all` and `test-day`",
+    },
+    Key {
+        name: "defaults.time",
+        kind: Kind::Bool,
+        help: "Pass `--time` to `solve` and `all`",
+    },
+    Key {
+        name: "defaults.allocs",
+        kind: Kind::Bool,
+        help: "Pass `--allocs` to `solve` and `all`",
+    },
+    Key {
+        name: "defaults.timeout",
+        kind: Kind::Integer,
+        help: "Pass `--timeout` to `solve` and `all`",
+    },
+];
+
+/// Benchmark bounds of `--time`: parts run for about `budget`, within the sample bounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    /// Core that `--stable` pins benchmarks to.
+    pub core: Option<usize>,
+}
+
+/// Layout of the benchmark table written by `all --release --time`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableConfig {
+    pub columns: Vec<Column>,
+    pub sort: Sort,
+    /// Number of slowest parts that are highlighted.
+    pub highlight: usize,
+    /// File that the table is written to instead of the readme.
+    pub output: Option<PathBuf>,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Time, Column::Allocs],
+            sort: Sort::Day,
+            highlight: 0,
+            output: None,
+        }
+    }
+}
+
+/// Flags that are passed to the commands by default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Defaults {
+    pub release: bool,
+    pub time: bool,
+    pub allocs: bool,
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    year: Option<Year>,
+    pub template: Option<String>,
+    pub session_file: Option<PathBuf>,
+    /// Key file for encrypted inputs, see [`crate::template::encryption`].
+    pub key_file: Option<PathBuf>,
+    pub data_dir: PathBuf,
+    pub readme: PathBuf,
+    pub bench: BenchConfig,
+    pub table: TableConfig,
+    pub defaults: Defaults,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            template: None,
+            session_file: None,
+            key_file: None,
+            data_dir: PathBuf::from("data"),
+            readme: PathBuf::from("README.md"),
+            bench: BenchConfig {
+                budget: Duration::from_secs(1),
+                min_samples: 10,
+                max_samples: 10000,
+                core: None,
+            },
+            table: TableConfig::default(),
+            defaults: Defaults::default(),
+        }
+    }
+}
+
+impl Config {
+    /// The year set via `AOC_YEAR` or the config file.
+    pub fn year(&self) -> Option<Year> {
+        Year::from_env().or(self.year)
+    }
+
+    /// The configured year, or the current year if none is configured.
+    pub fn year_or_current(&self, clock: &impl Clock) -> Year {
+        self.year().unwrap_or_else(|| Year::current(clock))
+    }
+
+    pub fn inputs_dir(&self) -> PathBuf {
+        self.data_dir.join("inputs")
+    }
+
+    pub fn examples_dir(&self) -> PathBuf {
+        self.data_dir.join("examples")
+    }
+
+    pub fn puzzles_dir(&self) -> PathBuf {
+        self.data_dir.join("puzzles")
+    }
+}
+
+/// Returns the project configuration. Exits if the config file is invalid.
+pub fn config() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+
+    CONFIG.get_or_init(|| {
+        read_config(Path::new(CONFIG_PATH)).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    })
+}
+
+/// Reads a config file, returns the default config if it does not exist.
+pub fn read_config(path: &Path) -> Result<Config, Error> {
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+
+    parse_config(&fs::read_to_string(path)?)
+}
+
+fn get<'a>(table: &'a toml::Table, key: &str) -> Option<&'a toml::Value> {
+    match key.split_once('.') {
+        Some((section, name)) => table.get(section)?.as_table()?.get(name),
+        None => table.get(key),
+    }
+}
+
+/// Checks that every setting of a table is known.
+fn check_keys(table: &toml::Table, prefix: &str) -> Result<(), Error> {
+    for (name, value) in table {
+        let key = format!("{prefix}{name}");
+
+        match value {
+            toml::Value::Table(section) if prefix.is_empty() => {
+                check_keys(section, &format!("{key}."))?;
+            }
+            _ if KEYS.iter().any(|x| x.name == key) => {}
+            _ => return Err(Error::Parser(format!("unknown setting `{key}`"))),
+        }
+    }
+
+    Ok(())
+}
+
+fn get_typed<'a, T>(
+    table: &'a toml::Table,
+    key: &str,
+    kind: Kind,
+    convert: impl Fn(&'a toml::Value) -> Option<T>,
+) -> Result<Option<T>, Error> {
+    get(table, key)
+        .map(|value| {
+            convert(value).ok_or_else(|| {
+                Error::Parser(format!("expected `{key}` to be {kind}, found `{value}`"))
+            })
+        })
+        .transpose()
+}
+
+fn get_integer(table: &toml::Table, key: &str) -> Result<Option<u64>, Error> {
+    get_typed(table, key, Kind::Integer, |x| {
+        x.as_integer().and_then(|x| u64::try_from(x).ok())
+    })
+}
+
+fn get_bool(table: &toml::Table, key: &str) -> Result<Option<bool>, Error> {
+    get_typed(table, key, Kind::Bool, toml::Value::as_bool)
+}
+
+fn get_string(table: &toml::Table, key: &str) -> Result<Option<String>, Error> {
+    get_typed(table, key, Kind::String, |x| x.as_str().map(String::from))
+}
+
+/// Parses the contents of a config file.
+pub fn parse_config(s: &str) -> Result<Config, Error> {
+    let table: toml::Table = s
+        .parse()
+        .map_err(|e: toml::de::Error| Error::Parser(e.to_string()))?;
+
+    check_keys(&table, "")?;
+
+    let default = Config::default();
+
+    let year = get_integer(&table, "year")?
+        .map(|x| {
+            u16::try_from(x)
+                .ok()
+                .and_then(Year::new)
+                .ok_or_else(|| Error::Parser(format!("`year` {x} is not a year of advent")))
+        })
+        .transpose()?;
+
+    let bench = BenchConfig {
+        budget: get_integer(&table, "bench.budget_ms")?
+            .map_or(default.bench.budget, Duration::from_millis),
+        min_samples: get_integer(&table, "bench.min_samples")?
+            .map_or(default.bench.min_samples, u128::from),
+        max_samples: get_integer(&table, "bench.max_samples")?
+            .map_or(default.bench.max_samples, u128::from),
+        core: get_integer(&table, "bench.core")?.map(|x| usize::try_from(x).unwrap_or(usize::MAX)),
+    };
+
+    if bench.min_samples == 0 || bench.min_samples > bench.max_samples {
+        return Err(Error::Parser(
+            "`bench.min_samples` must be between 1 and `bench.max_samples`".into(),
+        ));
+    }
+
+    let columns = get_string(&table, "table.columns")?
+        .map(|x| {
+            x.split(',')
+                .map(|x| x.trim().parse())
+                .collect::<Result<Vec<Column>, _>>()
+                .map_err(|e| Error::Parser(format!("`table.columns`: {e}")))
+        })
+        .transpose()?;
+
+    let sort = get_string(&table, "table.sort")?
+        .map(|x| {
+            x.parse()
+                .map_err(|e| Error::Parser(format!("`table.sort`: {e}")))
+        })
+        .transpose()?;
+
+    let table_config = TableConfig {
+        columns: columns.unwrap_or(default.table.columns),
+        sort: sort.unwrap_or(default.table.sort),
+        highlight: get_integer(&table, "table.highlight")?.map_or(default.table.highlight, |x| {
+            usize::try_from(x).unwrap_or(usize::MAX)
+        }),
+        output: get_string(&table, "table.output")?.map(PathBuf::from),
+    };
+
+    let timeout = get_integer(&table, "defaults.timeout")?;
+
+    if timeout == Some(0) {
+        return Err(Error::Parser(
+            "`defaults.timeout` must be at least 1 second".into(),
+        ));
+    }
+
+    Ok(Config {
+        year,
+        template: get_string(&table, "template")?,
+        session_file: get_string(&table, "session_file")?.map(PathBuf::from),
+        key_file: get_string(&table, "key_file")?.map(PathBuf::from),
+        data_dir: get_string(&table, "paths.data")?.map_or(default.data_dir, PathBuf::from),
+        readme: get_string(&table, "paths.readme")?.map_or(default.readme, PathBuf::from),
+        bench,
+        table: table_config,
+        defaults: Defaults {
+            release: get_bool(&table, "defaults.release")?.unwrap_or_default(),
+            time: get_bool(&table, "defaults.time")?.unwrap_or_default(),
+            allocs: get_bool(&table, "defaults.allocs")?.unwrap_or_default(),
+            timeout,
+        },
+    })
+}
+
+/// Formats the effective config, including defaults, as the contents of a config file.
+/// Settings without a value are commented out.
+#[must_use]
+pub fn format_config(config: &Config) -> String {
+    let quote = |x: &Path| toml::Value::String(x.to_string_lossy().into()).to_string();
+    let optional = |key: &str, value: Option<String>| match value {
+        Some(value) => format!("{key} = {value}\n"),
+        None => format!("# {key} =\n"),
+    };
+
+    let mut s = String::new();
+    s.push_str(&optional("year", config.year().map(|x| x.to_string())));
+    s.push_str(&optional(
+        "template",
+        config
+            .template
+            .as_ref()
+            .map(|x| toml::Value::String(x.clone()).to_string()),
+    ));
+    s.push_str(&optional(
+        "session_file",
+        config.session_file.as_deref().map(quote),
+    ));
+    s.push_str(&optional("key_file", config.key_file.as_deref().map(quote)));
+
+    let _ = write!(
+        s,
+        "\n[paths]\ndata = {}\nreadme = {}\n",
+        quote(&config.data_dir),
+        quote(&config.readme)
+    );
+
+    let _ = write!(
+        s,
+        "\n[bench]\nbudget_ms = {}\nmin_samples = {}\nmax_samples = {}\n",
+        config.bench.budget.as_millis(),
+        config.bench.min_samples,
+        config.bench.max_samples
+    );
+    s.push_str(&optional("core", config.bench.core.map(|x| x.to_string())));
+
+    let columns: Vec<String> = config
+        .table
+        .columns
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let _ = write!(
+        s,
+        "\n[table]\ncolumns = {}\nsort = \"{}\"\nhighlight = {}\n",
+        toml::Value::String(columns.join(",")),
+        config.table.sort,
+        config.table.highlight
+    );
+    s.push_str(&optional(
+        "output",
+        config.table.output.as_deref().map(quote),
+    ));
+
+    let defaults = &config.defaults;
+    let _ = write!(
+        s,
+        "\n[defaults]\nrelease = {}\ntime = {}\nallocs = {}\n",
+        defaults.release, defaults.time, defaults.allocs
+    );
+    s.push_str(&optional(
+        "timeout",
+        defaults.timeout.map(|x| x.to_string()),
+    ));
+
+    s
+}
+
+/// Sets a setting in the contents of a config file, keeping its formatting and comments.
+pub fn set_value(contents: &str, key: &str, value: &str) -> Result<String, Error> {
+    let Some(setting) = KEYS.iter().find(|x| x.name == key) else {
+        return Err(Error::Parser(format!("unknown setting `{key}`")));
+    };
+
+    let invalid = || Error::Parser(format!("expected `{key}` to be {}", setting.kind));
+
+    let value = match setting.kind {
+        Kind::Integer => toml_edit::value(value.parse::<i64>().map_err(|_| invalid())?),
+        Kind::Bool => toml_edit::value(value.parse::<bool>().map_err(|_| invalid())?),
+        Kind::String => toml_edit::value(value),
+    };
+
+    let mut document: toml_edit::DocumentMut = contents
+        .parse()
+        .map_err(|e: toml_edit::TomlError| Error::Parser(e.to_string()))?;
+
+    match key.split_once('.') {
+        Some((section, name)) => {
+            let table = document
+                .entry(section)
+                .or_insert_with(toml_edit::table)
+                .as_table_mut()
+                .ok_or_else(|| Error::Parser(format!("`{section}` must be a table")))?;
+            table[name] = value;
+        }
+        // top-level settings are written before the tables of the document.
+        None => document[key] = value,
+    }
+
+    let contents = document.to_string();
+    // reject values that are valid TOML, but not a valid config, e.g. a year before 2015.
+    parse_config(&contents)?;
+    Ok(contents)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_config, parse_config, set_value, Config, Error};
+    use crate::template::readme_benchmarks::{Column, Sort};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_empty_config() {
+        assert_eq!(parse_config("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn parses_config() {
+        let config = parse_config(
+            "template = \"grid\"\n[paths]\ndata = \"puzzles\"\n[bench]\nbudget_ms = 200\ncore = 2\n[defaults]\nrelease = true\ntimeout = 10\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.template.as_deref(), Some("grid"));
+        assert_eq!(config.data_dir, PathBuf::from("puzzles"));
+        assert_eq!(config.inputs_dir(), PathBuf::from("puzzles/inputs"));
+        assert_eq!(config.bench.budget, Duration::from_millis(200));
+        assert_eq!(config.bench.max_samples, 10000);
+        assert_eq!(config.bench.core, Some(2));
+        assert!(config.defaults.release);
+        assert!(!config.defaults.time);
+        assert_eq!(config.defaults.timeout, Some(10));
+    }
+
+    #[test]
+    fn parses_table_config() {
+        let config = parse_config(
+            "[table]\ncolumns = \"time, share\"\nsort = \"slowest\"\nhighlight = 2\noutput = \"bench.html\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.table.columns, vec![Column::Time, Column::Share]);
+        assert_eq!(config.table.sort, Sort::Slowest);
+        assert_eq!(config.table.highlight, 2);
+        assert_eq!(config.table.output, Some(PathBuf::from("bench.html")));
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        for s in [
+            "yaer = 2023",
+            "[bench]\nbudget = 10",
+            "year = 2000",
+            "template = 1",
+            "[defaults]\ntimeout = -1",
+            "[defaults]\ntimeout = 0",
+            "[bench]\nmin_samples = 100\nmax_samples = 10",
+            "[table]\ncolumns = \"time,speed\"",
+            "[table]\nsort = \"random\"",
+        ] {
+            assert!(matches!(parse_config(s), Err(Error::Parser(_))), "{s}");
+        }
+    }
+
+    #[test]
+    fn formats_config_that_parses() {
+        let config = parse_config(
+            "template = \"grid\"\n[table]\nsort = \"fastest\"\n[defaults]\ntimeout = 5\n",
+        )
+        .unwrap();
+        let formatted = format_config(&config);
+
+        assert!(formatted.contains("# session_file =\n"));
+
+        // the year is not compared, as it may be set via `AOC_YEAR`.
+        let parsed = parse_config(&formatted).unwrap();
+        assert_eq!(parsed.template, config.template);
+        assert_eq!(parsed.bench, config.bench);
+        assert_eq!(parsed.table, config.table);
+        assert_eq!(parsed.defaults, config.defaults);
+    }
+
+    #[test]
+    fn sets_values() {
+        let s = "# my config\n[bench]\nbudget_ms = 200 # fast\n";
+
+        let s = set_value(s, "bench.max_samples", "50").unwrap();
+        let s = set_value(&s, "defaults.release", "true").unwrap();
+        let s = set_value(&s, "template", "grid").unwrap();
+
+        assert!(s.starts_with("template = \"grid\"\n# my config\n"));
+        assert!(s.contains("budget_ms = 200 # fast\nmax_samples = 50\n"));
+
+        let config = parse_config(&s).unwrap();
+        assert_eq!(config.bench.max_samples, 50);
+        assert!(config.defaults.release);
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        assert!(set_value("", "bench.budget_ms", "fast").is_err());
+        assert!(set_value("", "defaults.release", "yes").is_err());
+        assert!(set_value("", "year", "1999").is_err());
+        assert!(set_value("", "colors", "true").is_err());
+    }
+}
@@ -0,0 +1,262 @@
+/// Differential testing of the named variants of a solution, see `crosscheck DD`.
+///
+/// Every variant of a part runs on every example that has an expected answer for that part, on the
+/// real input and on the named inputs. Answers are compared as strings, as variants may return
+/// different types.
+use std::fmt::{Display, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::{env, fs, io};
+
+use crate::template::config::config;
+use crate::template::{encryption, examples, inputs};
+use crate::{Day, Part};
+
+#[derive(Debug)]
+pub enum Error {
+    Examples(examples::Error),
+    Inputs(inputs::Error),
+    Encryption(encryption::Error),
+    IO(io::Error),
+}
+
+impl From<examples::Error> for Error {
+    fn from(e: examples::Error) -> Self {
+        Error::Examples(e)
+    }
+}
+
+impl From<inputs::Error> for Error {
+    fn from(e: inputs::Error) -> Self {
+        Error::Inputs(e)
+    }
+}
+
+impl From<encryption::Error> for Error {
+    fn from(e: encryption::Error) -> Self {
+        Error::Encryption(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Examples(e) => write!(f, "{e}"),
+            Error::Inputs(e) => write!(f, "{e}"),
+            Error::Encryption(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "could not read input: {e}"),
+        }
+    }
+}
+
+/// The name of the implementation that is not a variant, i.e. `part_one` or `part_two`.
+pub const DEFAULT: &str = "default";
+
+/// A named implementation of a part.
+pub type Variant<'a> = (&'static str, Box<dyn Fn(&str) -> Option<String> + 'a>);
+
+/// Boxes a variant and converts its answers to strings.
+pub fn variant<'a, T: Display>(
+    name: &'static str,
+    func: impl Fn(&str) -> Option<T> + 'a,
+) -> Variant<'a> {
+    (
+        name,
+        Box::new(move |input| func(input).map(|x| x.to_string())),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Value(String),
+    /// The part returned `None`.
+    Missing,
+    Panic,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Value(value) => f.write_str(value),
+            Answer::Missing => f.write_str("✖"),
+            Answer::Panic => f.write_str("panicked"),
+        }
+    }
+}
+
+/// The answers of all variants of a part for one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub source: String,
+    pub part: Part,
+    pub answers: Vec<(&'static str, Answer)>,
+}
+
+impl Check {
+    #[must_use]
+    pub fn agrees(&self) -> bool {
+        self.answers.windows(2).all(|x| x[0].1 == x[1].1)
+    }
+}
+
+fn run_variants(variants: &[Variant], input: &str) -> Vec<(&'static str, Answer)> {
+    variants
+        .iter()
+        .map(|(name, func)| {
+            let answer = match panic::catch_unwind(AssertUnwindSafe(|| func(input))) {
+                Ok(Some(value)) => Answer::Value(value),
+                Ok(None) => Answer::Missing,
+                Err(_) => Answer::Panic,
+            };
+            (*name, answer)
+        })
+        .collect()
+}
+
+/// Runs the variants of both parts on the examples, the input and the named inputs of a day.
+/// Parts without variants besides the default are not checked.
+pub fn crosscheck(
+    day: Day,
+    part_one: &[Variant],
+    part_two: &[Variant],
+) -> Result<Vec<Check>, Error> {
+    let cwd = env::current_dir()?;
+
+    // the input, and whether part one and two are checked against it.
+    let mut sources: Vec<(String, String, bool, bool)> = vec![];
+
+    for example in examples::read_manifest(day)? {
+        let input = fs::read_to_string(cwd.join(config().examples_dir()).join(&example.file))?;
+        let (one, two) = (example.part_one.is_some(), example.part_two.is_some());
+        sources.push((example.file, input, one, two));
+    }
+
+    let input_path = cwd.join(config().inputs_dir()).join(format!("{day}.txt"));
+    if encryption::exists(&input_path) {
+        sources.push((
+            format!("{day}.txt (input)"),
+            encryption::read_input_file(&input_path)?,
+            true,
+            true,
+        ));
+    }
+
+    for input in inputs::read_inputs(day)? {
+        let source = format!("{}/{}.txt", day, input.name);
+        let input_text = encryption::read_input_file(&input.path)?;
+        sources.push((source, input_text, true, true));
+    }
+
+    let mut checks = vec![];
+
+    for (source, input, one, two) in sources {
+        for (part, variants, enabled) in [(Part::One, part_one, one), (Part::Two, part_two, two)] {
+            if enabled && variants.len() > 1 {
+                checks.push(Check {
+                    source: source.clone(),
+                    part,
+                    answers: run_variants(variants, &input),
+                });
+            }
+        }
+    }
+
+    Ok(checks)
+}
+
+/// Formats one line per check, listing the answer of every variant if they disagree.
+#[must_use]
+pub fn format_report(checks: &[Check]) -> String {
+    let mut s = String::new();
+
+    for check in checks {
+        let names: Vec<&str> = check.answers.iter().map(|x| x.0).collect();
+
+        let _ = if check.agrees() {
+            writeln!(
+                s,
+                "✔ {}, part {}: {} ({})",
+                check.source,
+                check.part,
+                check.answers[0].1,
+                names.join(", ")
+            )
+        } else {
+            let answers: Vec<String> = check
+                .answers
+                .iter()
+                .map(|(name, answer)| format!("{name} = {answer}"))
+                .collect();
+            writeln!(
+                s,
+                "✖ {}, part {}: {}",
+                check.source,
+                check.part,
+                answers.join(", ")
+            )
+        };
+    }
+
+    s
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_report, run_variants, variant, Answer, Check, Variant};
+    use crate::Part;
+
+    fn get_variants() -> Vec<Variant<'static>> {
+        vec![
+            variant("default", |input: &str| Some(input.len())),
+            variant("fast", |input: &str| {
+                Some(input.lines().map(str::len).sum::<usize>() + 1)
+            }),
+            variant("broken", |_: &str| -> Option<u32> { panic!("oops") }),
+        ]
+    }
+
+    #[test]
+    fn runs_variants() {
+        let answers = run_variants(&get_variants(), "ab\ncd");
+        assert_eq!(
+            answers,
+            vec![
+                ("default", Answer::Value("5".into())),
+                ("fast", Answer::Value("5".into())),
+                ("broken", Answer::Panic),
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_report() {
+        let agreeing = Check {
+            source: "01-1.txt".into(),
+            part: Part::One,
+            answers: vec![
+                ("default", Answer::Value("42".into())),
+                ("fast", Answer::Value("42".into())),
+            ],
+        };
+        let disagreeing = Check {
+            source: "01.txt (input)".into(),
+            part: Part::Two,
+            answers: vec![
+                ("default", Answer::Value("42".into())),
+                ("fast", Answer::Missing),
+            ],
+        };
+
+        assert!(agreeing.agrees());
+        assert!(!disagreeing.agrees());
+        assert_eq!(
+            format_report(&[agreeing, disagreeing]),
+            "✔ 01-1.txt, part 1: 42 (default, fast)\n✖ 01.txt (input), part 2: default = 42, fast = ✖\n"
+        );
+    }
+}
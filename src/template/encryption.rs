@@ -0,0 +1,386 @@
+/// Encrypted puzzle inputs, so that they can be committed without publishing them.
+///
+/// An input `DD.txt` is stored as `DD.txt.enc`, encrypted with ChaCha20-Poly1305 and a 256-bit key shared
+/// by the team. The key is read from the `AOC_INPUT_KEY` environment variable, or from the key file,
+/// `.aoc/input.key` unless `key_file` is set in the project config, as 64 hex digits.
+///
+/// Inputs are read through [`read_input_file`], which prefers the encrypted file over the plain text. The path relative to the inputs directory, e.g. `01/alice.txt`, is authenticated along with
+/// the input, so encrypted inputs cannot be swapped between days or named inputs.
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+
+use crate::template::cli;
+use crate::template::config::config;
+
+pub const KEY_ENV: &str = "AOC_INPUT_KEY";
+pub const DEFAULT_KEY_FILE: &str = ".aoc/input.key";
+pub const EXTENSION: &str = "enc";
+
+/// Identifies the format of encrypted files, followed by the nonce and the ciphertext.
+const MAGIC: &[u8] = b"aoc-enc1";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Neither the environment variable nor the key file is set.
+    MissingKey,
+    InvalidKey(String),
+    /// The file is corrupted, or was encrypted with another key.
+    Decrypt(PathBuf),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingKey => write!(
+                f,
+                "no input key, set {KEY_ENV} or create \"{}\"",
+                get_key_path().display()
+            ),
+            Error::InvalidKey(e) => write!(f, "invalid input key: {e}"),
+            Error::Decrypt(path) => write!(
+                f,
+                "could not decrypt \"{}\": wrong key or corrupted file",
+                path.display()
+            ),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A 256-bit key. Not `Debug`, so that it does not end up in logs by accident.
+pub struct Key([u8; 32]);
+
+impl Key {
+    /// Generates a random key.
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(ChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    /// Formats the key as 64 hex digits.
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|x| format!("{x:02x}")).collect()
+    }
+}
+
+/// Parses a key from 64 hex digits, surrounding whitespace is ignored.
+pub fn parse_key(s: &str) -> Result<Key, Error> {
+    let s = s.trim();
+
+    if s.len() != 64 || !s.is_ascii() {
+        return Err(Error::InvalidKey(format!(
+            "expected 64 hex digits, found {} characters",
+            s.chars().count()
+        )));
+    }
+
+    let mut key = [0; 32];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16)
+            .map_err(|_| Error::InvalidKey("expected 64 hex digits".into()))?;
+    }
+
+    Ok(Key(key))
+}
+
+/// The key file of the project config, expanding a leading `~/`.
+#[must_use]
+pub fn get_key_path() -> PathBuf {
+    let Some(path) = &config().key_file else {
+        return PathBuf::from(DEFAULT_KEY_FILE);
+    };
+
+    match (path.strip_prefix("~"), env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.clone(),
+    }
+}
+
+/// Loads the key from the environment or the key file. Returns `None` if neither is set.
+pub fn load_key() -> Result<Option<Key>, Error> {
+    if let Ok(key) = env::var(KEY_ENV) {
+        return parse_key(&key).map(Some);
+    }
+
+    let path = get_key_path();
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    parse_key(&fs::read_to_string(path)?).map(Some)
+}
+
+/// The path of the encrypted form of a file, e.g. `data/inputs/01.txt.enc`.
+#[must_use]
+pub fn get_encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{EXTENSION}"));
+    PathBuf::from(name)
+}
+
+/// The name that is authenticated with the contents of a file: its path relative to `inputs_dir` without
+/// `.enc`, with `/` as separator. Files outside of `inputs_dir` are identified by their file name.
+fn associated_data(inputs_dir: &Path, path: &Path) -> Vec<u8> {
+    let absolute = env::current_dir().map(|x| x.join(inputs_dir)).ok();
+
+    let relative = [Some(inputs_dir), absolute.as_deref()]
+        .into_iter()
+        .flatten()
+        .find_map(|dir| path.strip_prefix(dir).ok())
+        .or_else(|| path.file_name().map(Path::new))
+        .unwrap_or(path);
+
+    relative
+        .iter()
+        .map(|x| x.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+        .trim_end_matches(&format!(".{EXTENSION}"))
+        .as_bytes()
+        .to_vec()
+}
+
+/// Encrypts the contents of the file at `path`, which is authenticated by its path.
+#[must_use]
+pub fn encrypt(key: &Key, path: &Path, plaintext: &[u8]) -> Vec<u8> {
+    let cipher = ChaCha20Poly1305::new(&key.0.into());
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let aad = associated_data(&config().inputs_dir(), path);
+
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad: &aad,
+            },
+        )
+        .expect("inputs fit into a single message");
+
+    [MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+/// Decrypts the contents of the encrypted file at `path`.
+pub fn decrypt(key: &Key, path: &Path, data: &[u8]) -> Result<Vec<u8>, Error> {
+    let error = || Error::Decrypt(path.to_path_buf());
+
+    let data = data.strip_prefix(MAGIC).ok_or_else(error)?;
+    if data.len() < NONCE_LEN {
+        return Err(error());
+    }
+
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let cipher = ChaCha20Poly1305::new(&key.0.into());
+    let aad = associated_data(&config().inputs_dir(), path);
+
+    cipher
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: &aad,
+            },
+        )
+        .map_err(|_| error())
+}
+
+/// Encrypts the file at `path` to its encrypted path, which is returned. The plain text is kept.
+pub fn encrypt_file(key: &Key, path: &Path) -> Result<PathBuf, Error> {
+    let encrypted_path = get_encrypted_path(path);
+    let data = encrypt(key, &encrypted_path, &fs::read(path)?);
+    fs::write(&encrypted_path, data)?;
+    Ok(encrypted_path)
+}
+
+/// The input files in the inputs directory and the directories of named inputs whose name ends in `suffix`,
+/// e.g. `.txt`, sorted by path.
+pub fn find_input_files(suffix: &str) -> io::Result<Vec<PathBuf>> {
+    let inputs_dir = config().inputs_dir();
+    let mut dirs = vec![inputs_dir.clone()];
+    let mut files = vec![];
+
+    if !inputs_dir.is_dir() {
+        return Ok(files);
+    }
+
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+
+            if path.is_dir() && dir == inputs_dir {
+                dirs.push(path);
+            } else if path.is_file() && path.to_string_lossy().ends_with(suffix) {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Whether the file or its encrypted form exists.
+#[must_use]
+pub fn exists(path: &Path) -> bool {
+    path.exists() || get_encrypted_path(path).exists()
+}
+
+/// Reads a text file, or decrypts its encrypted form if it exists. An empty plain text is a placeholder
+/// and ignored, one that differs from the encrypted form is stale and ignored with a warning. The plain
+/// text is only used without the encrypted form, or if it cannot be decrypted, e.g. without a key.
+pub fn read_input_file(path: &Path) -> Result<String, Error> {
+    read_input(path, load_key)
+}
+
+fn read_input(
+    path: &Path,
+    load_key: impl Fn() -> Result<Option<Key>, Error>,
+) -> Result<String, Error> {
+    let encrypted_path = get_encrypted_path(path);
+
+    if !encrypted_path.exists() {
+        return Ok(fs::read_to_string(path)?);
+    }
+
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) if !contents.is_empty() => contents,
+        _ => return read_encrypted(&encrypted_path, &load_key),
+    };
+
+    match read_encrypted(&encrypted_path, &load_key) {
+        Ok(decrypted) => {
+            if decrypted != contents {
+                eprintln!(
+                    "⚠️ \"{}\" differs from its encrypted form, using the encrypted input. Run `cargo decrypt-inputs {}` to replace it, or `cargo encrypt-inputs` to update the encrypted input.",
+                    path.display(),
+                    cli::DECRYPT_FORCE.name
+                );
+            }
+            Ok(decrypted)
+        }
+        Err(_) => Ok(contents),
+    }
+}
+
+/// Decrypts an encrypted input file to a string.
+fn read_encrypted(
+    encrypted_path: &Path,
+    load_key: impl Fn() -> Result<Option<Key>, Error>,
+) -> Result<String, Error> {
+    let key = load_key()?.ok_or(Error::MissingKey)?;
+    let plaintext = decrypt(&key, encrypted_path, &fs::read(encrypted_path)?)?;
+
+    String::from_utf8(plaintext)
+        .map_err(|e| Error::IO(io::Error::new(io::ErrorKind::InvalidData, e)))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        associated_data, decrypt, encrypt, get_encrypted_path, parse_key, read_input, Key,
+    };
+    use std::path::Path;
+    use std::{env, fs, process};
+
+    #[test]
+    fn round_trips() {
+        let key = Key::generate();
+        let path = Path::new("data/inputs/01.txt.enc");
+        let data = encrypt(&key, path, b"1abc2\npqr3stu8vwx\n");
+
+        assert_ne!(&data[data.len() - 18..], b"1abc2\npqr3stu8vwx\n");
+        assert_eq!(decrypt(&key, path, &data).unwrap(), b"1abc2\npqr3stu8vwx\n");
+    }
+
+    #[test]
+    fn rejects_wrong_key_and_name() {
+        let key = Key::generate();
+        let data = encrypt(&key, Path::new("01.txt.enc"), b"input");
+
+        assert!(decrypt(&Key::generate(), Path::new("01.txt.enc"), &data).is_err());
+        // encrypted inputs cannot be swapped between days.
+        assert!(decrypt(&key, Path::new("02.txt.enc"), &data).is_err());
+        assert!(decrypt(&key, Path::new("01.txt.enc"), &data[..10]).is_err());
+    }
+
+    #[test]
+    fn authenticates_path_in_inputs_dir() {
+        let dir = Path::new("data/inputs");
+        let aad = |path: &str| String::from_utf8(associated_data(dir, Path::new(path))).unwrap();
+
+        assert_eq!(aad("data/inputs/01.txt.enc"), "01.txt");
+        assert_eq!(aad("data/inputs/01/alice.txt.enc"), "01/alice.txt");
+        assert_ne!(
+            aad("data/inputs/01/alice.txt.enc"),
+            aad("data/inputs/02/alice.txt.enc")
+        );
+        assert_eq!(
+            aad(&env::current_dir()
+                .unwrap()
+                .join("data/inputs/01/alice.txt.enc")
+                .to_string_lossy()),
+            "01/alice.txt"
+        );
+        assert_eq!(aad("elsewhere/01.txt.enc"), "01.txt");
+    }
+
+    #[test]
+    fn prefers_encrypted_input() {
+        let dir = env::temp_dir().join(format!("aoc-encryption-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let key = Key::generate();
+        let path = dir.join("06.txt");
+        let encrypted_path = get_encrypted_path(&path);
+        fs::write(&encrypted_path, encrypt(&key, &encrypted_path, b"input")).unwrap();
+
+        let read = |with_key: bool| {
+            read_input(&path, || Ok(with_key.then(|| Key(key.0)))).map_err(|e| e.to_string())
+        };
+
+        // a placeholder, e.g. from `scaffold`.
+        fs::write(&path, "").unwrap();
+        assert_eq!(read(true).unwrap(), "input");
+        assert!(read(false).is_err());
+
+        // a stale plain text.
+        fs::write(&path, "old input").unwrap();
+        assert_eq!(read(true).unwrap(), "input");
+        assert_eq!(read(false).unwrap(), "old input");
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read(true).unwrap(), "input");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn parses_keys() {
+        let key = Key::generate();
+        assert_eq!(parse_key(&format!("{}\n", key.to_hex())).unwrap().0, key.0);
+        assert!(parse_key("abc").is_err());
+        assert!(parse_key(&"g".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn appends_extension() {
+        assert_eq!(
+            get_encrypted_path(Path::new("data/inputs/01.txt")),
+            Path::new("data/inputs/01.txt.enc")
+        );
+    }
+}
@@ -0,0 +1,389 @@
+/// Example manifests: a `data/examples/DD.toml` file per day that lists example files and their expected answers.
+///
+/// ```toml
+/// [[example]]
+/// file = "08-1.txt"
+/// part_one = 2
+///
+/// [[example]]
+/// file = "08-3.txt"
+/// part_two = 6
+/// ```
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+use crate::template::config::config;
+use crate::template::table;
+use crate::Day;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "invalid example manifest: {e}"),
+            Error::IO(e) => write!(f, "could not read example manifest: {e}"),
+        }
+    }
+}
+
+/// An example input and the answers it is expected to produce.
+/// A part without an expected answer is not checked for this example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub file: String,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+/// The outcome of running a part against an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass(String),
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    Panic,
+    /// The example has no expected answer for this part.
+    Skipped,
+}
+
+impl Outcome {
+    #[must_use]
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Outcome::Fail { .. } | Outcome::Panic)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Pass(actual) => write!(f, "✔ {actual}"),
+            Outcome::Fail { expected, actual } => write!(
+                f,
+                "✖ {} (expected {expected})",
+                actual.as_deref().unwrap_or("None")
+            ),
+            Outcome::Panic => write!(f, "✖ panicked"),
+            Outcome::Skipped => write!(f, "-"),
+        }
+    }
+}
+
+/// The results of running both parts against an example.
+#[derive(Debug, Clone)]
+pub struct ExampleResult {
+    pub file: String,
+    pub part_one: Outcome,
+    pub part_two: Outcome,
+}
+
+#[must_use]
+pub fn get_manifest_path(day: Day) -> PathBuf {
+    config().examples_dir().join(format!("{day}.toml"))
+}
+
+/// Answers may be written as integers or strings.
+pub(crate) fn answer_to_string(value: &toml::Value) -> Result<String, String> {
+    match value {
+        toml::Value::Integer(x) => Ok(x.to_string()),
+        toml::Value::String(x) => Ok(x.clone()),
+        x => Err(format!(
+            "expected answer to be an integer or a string, found `{x}`"
+        )),
+    }
+}
+
+/// Parses the contents of an example manifest.
+pub fn parse_manifest(s: &str) -> Result<Vec<Example>, Error> {
+    let table: toml::Table = s
+        .parse()
+        .map_err(|e: toml::de::Error| Error::Parser(e.to_string()))?;
+
+    let Some(examples) = table.get("example") else {
+        return Ok(vec![]);
+    };
+
+    let examples = examples
+        .as_array()
+        .ok_or_else(|| Error::Parser("`example` must be an array of tables".into()))?;
+
+    examples
+        .iter()
+        .map(|example| {
+            let example = example
+                .as_table()
+                .ok_or_else(|| Error::Parser("`example` must be an array of tables".into()))?;
+
+            let file = example
+                .get("file")
+                .and_then(toml::Value::as_str)
+                .ok_or_else(|| Error::Parser("every example needs a `file`".into()))?;
+
+            Ok(Example {
+                file: file.into(),
+                part_one: example
+                    .get("part_one")
+                    .map(answer_to_string)
+                    .transpose()
+                    .map_err(Error::Parser)?,
+                part_two: example
+                    .get("part_two")
+                    .map(answer_to_string)
+                    .transpose()
+                    .map_err(Error::Parser)?,
+            })
+        })
+        .collect()
+}
+
+/// Reads the example manifest of a day. Returns no examples if the day has no manifest.
+pub fn read_manifest(day: Day) -> Result<Vec<Example>, Error> {
+    let path = env::current_dir()?.join(get_manifest_path(day));
+
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    parse_manifest(&fs::read_to_string(path)?)
+}
+
+fn format_answer(answer: &str) -> String {
+    // answers that fit a TOML integer are written unquoted.
+    if answer.parse::<i64>().is_ok() {
+        answer.to_string()
+    } else {
+        toml::Value::String(answer.to_string()).to_string()
+    }
+}
+
+/// Formats examples as the contents of an example manifest.
+#[must_use]
+pub fn format_manifest(examples: &[Example]) -> String {
+    examples
+        .iter()
+        .map(|example| {
+            let mut lines = vec![
+                "[[example]]".to_string(),
+                format!("file = {}", toml::Value::String(example.file.clone())),
+            ];
+
+            if let Some(answer) = &example.part_one {
+                lines.push(format!("part_one = {}", format_answer(answer)));
+            }
+
+            if let Some(answer) = &example.part_two {
+                lines.push(format!("part_two = {}", format_answer(answer)));
+            }
+
+            lines.join("\n") + "\n"
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Writes the example manifest of a day, replacing an existing one.
+pub fn write_manifest(day: Day, examples: &[Example]) -> Result<(), Error> {
+    let path = env::current_dir()?.join(get_manifest_path(day));
+    fs::write(path, format_manifest(examples))?;
+    Ok(())
+}
+
+fn run_example_part<T: Display>(
+    func: impl Fn(&str) -> Option<T>,
+    input: &str,
+    expected: Option<&String>,
+) -> Outcome {
+    let Some(expected) = expected else {
+        return Outcome::Skipped;
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| func(input))) {
+        Ok(result) => {
+            let actual = result.map(|x| x.to_string());
+            if actual.as_ref() == Some(expected) {
+                Outcome::Pass(expected.clone())
+            } else {
+                Outcome::Fail {
+                    expected: expected.clone(),
+                    actual,
+                }
+            }
+        }
+        Err(_) => Outcome::Panic,
+    }
+}
+
+/// Runs both parts against every example in the manifest of a day.
+pub fn run_examples<T1: Display, T2: Display>(
+    day: Day,
+    part_one: impl Fn(&str) -> Option<T1>,
+    part_two: impl Fn(&str) -> Option<T2>,
+) -> Result<Vec<ExampleResult>, Error> {
+    read_manifest(day)?
+        .into_iter()
+        .map(|example| {
+            let path = env::current_dir()?
+                .join(config().examples_dir())
+                .join(&example.file);
+            let input = fs::read_to_string(path)?;
+
+            Ok(ExampleResult {
+                part_one: run_example_part(&part_one, &input, example.part_one.as_ref()),
+                part_two: run_example_part(&part_two, &input, example.part_two.as_ref()),
+                file: example.file,
+            })
+        })
+        .collect()
+}
+
+/// Formats example results as a table with one row per example and one column per part.
+#[must_use]
+pub fn format_matrix(results: &[ExampleResult]) -> String {
+    let rows: Vec<[String; 3]> = results
+        .iter()
+        .map(|x| {
+            [
+                x.file.clone(),
+                x.part_one.to_string(),
+                x.part_two.to_string(),
+            ]
+        })
+        .collect();
+
+    table::format_columns(["Example", "Part 1", "Part 2"], &rows, " | ")
+}
+
+/// Test harness generated by [`crate::solution`]: checks every example in the manifest and
+/// panics with the result matrix if any of them fails.
+pub fn assert_examples<T1: Display, T2: Display>(
+    day: Day,
+    part_one: impl Fn(&str) -> Option<T1>,
+    part_two: impl Fn(&str) -> Option<T2>,
+) {
+    let results = match run_examples(day, part_one, part_two) {
+        Ok(results) => results,
+        Err(e) => panic!("{e}"),
+    };
+
+    if results
+        .iter()
+        .any(|x| x.part_one.is_failure() || x.part_two.is_failure())
+    {
+        panic!("examples failed:\n{}", format_matrix(&results));
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_manifest, format_matrix, parse_manifest, Example, ExampleResult, Outcome};
+
+    #[test]
+    fn parses_manifest() {
+        let manifest = parse_manifest(
+            r#"
+            [[example]]
+            file = "08-1.txt"
+            part_one = 2
+
+            [[example]]
+            file = "08-3.txt"
+            part_two = "ABC"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest,
+            vec![
+                Example {
+                    file: "08-1.txt".into(),
+                    part_one: Some("2".into()),
+                    part_two: None
+                },
+                Example {
+                    file: "08-3.txt".into(),
+                    part_one: None,
+                    part_two: Some("ABC".into())
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_empty_manifest() {
+        assert_eq!(parse_manifest("").unwrap(), vec![]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_file_missing() {
+        parse_manifest("[[example]]\npart_one = 1").unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_on_invalid_answer() {
+        parse_manifest("[[example]]\nfile = \"01.txt\"\npart_one = 1.5").unwrap();
+    }
+
+    #[test]
+    fn formats_manifest() {
+        let examples = vec![
+            Example {
+                file: "08-1.txt".into(),
+                part_one: Some("2".into()),
+                part_two: None,
+            },
+            Example {
+                file: "08-2.txt".into(),
+                part_one: None,
+                part_two: Some("ABC".into()),
+            },
+        ];
+
+        let manifest = format_manifest(&examples);
+
+        assert_eq!(
+            manifest,
+            "[[example]]\nfile = \"08-1.txt\"\npart_one = 2\n\n[[example]]\nfile = \"08-2.txt\"\npart_two = \"ABC\"\n"
+        );
+        assert_eq!(parse_manifest(&manifest).unwrap(), examples);
+    }
+
+    #[test]
+    fn formats_matrix() {
+        let matrix = format_matrix(&[
+            ExampleResult {
+                file: "08-1.txt".into(),
+                part_one: Outcome::Pass("2".into()),
+                part_two: Outcome::Skipped,
+            },
+            ExampleResult {
+                file: "08-3.txt".into(),
+                part_one: Outcome::Skipped,
+                part_two: Outcome::Fail {
+                    expected: "6".into(),
+                    actual: None,
+                },
+            },
+        ]);
+
+        let lines: Vec<&str> = matrix.lines().collect();
+        assert_eq!(lines[1], "08-1.txt | ✔ 2    | -");
+        assert_eq!(lines[2], "08-3.txt | -      | ✖ None (expected 6)");
+    }
+}
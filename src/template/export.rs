@@ -0,0 +1,349 @@
+/// Exports the timings of `all --time` as JSON or CSV, together with metadata about the environment
+/// they were measured in, so that results can be compared across machines.
+use std::fmt::Write;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, UNIX_EPOCH};
+use std::{env, fs, io, thread};
+
+use serde_json::{json, Value};
+
+use crate::template::readme_benchmarks::{Timing, Timings};
+use crate::Clock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+    /// Derives the format from the extension of a file, `.json` or `.csv`.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// The machine and build that timings were measured with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Environment {
+    pub rustc: Option<String>,
+    pub profile: &'static str,
+    pub alloc_tracking: bool,
+    pub cpu: Option<String>,
+    pub cores: usize,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub commit: Option<String>,
+    /// Whether the working tree has uncommitted changes.
+    pub dirty: Option<bool>,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The settings of `--stable`, if it was passed.
+    pub stable: Option<String>,
+}
+
+impl Environment {
+    pub fn collect(is_release: bool, track_allocs: bool, clock: &impl Clock) -> Self {
+        Self {
+            rustc: command_output("rustc", &["--version"]),
+            profile: if is_release { "release" } else { "debug" },
+            alloc_tracking: track_allocs,
+            cpu: cpu_model(),
+            cores: thread::available_parallelism().map_or(1, usize::from),
+            os: env::consts::OS,
+            arch: env::consts::ARCH,
+            commit: command_output("git", &["rev-parse", "HEAD"]),
+            dirty: command_output("git", &["status", "--porcelain"]).map(|x| !x.is_empty()),
+            timestamp: clock
+                .now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |x| x.as_secs()),
+            stable: None,
+        }
+    }
+}
+
+/// Runs a command and returns its trimmed stdout if it succeeds.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn cpu_model() -> Option<String> {
+    fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|x| parse_cpu_model(&x))
+        // macOS does not have procfs.
+        .or_else(|| command_output("sysctl", &["-n", "machdep.cpu.brand_string"]))
+}
+
+fn parse_cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "model name").then(|| value.trim().to_string())
+    })
+}
+
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+fn format_timing(timing: &Timing) -> Value {
+    match timing {
+        Timing::Measured(x) => json!({
+            "timed_out": false,
+            "mean_ns": nanos(x.mean),
+            "median_ns": x.median.map(nanos),
+            "stddev_ns": x.stddev.map(nanos),
+            "samples": u64::try_from(x.samples).unwrap_or(u64::MAX),
+            "allocs": x.allocs.map(|x| x.allocations),
+            "alloc_bytes": x.allocs.map(|x| x.bytes),
+            "peak_bytes": x.allocs.map(|x| x.peak_bytes),
+        }),
+        Timing::TimedOut(timeout) => json!({
+            "timed_out": true,
+            "timeout_ns": nanos(*timeout),
+        }),
+    }
+}
+
+#[must_use]
+pub fn format_json(timings: &[Timings], environment: &Environment) -> String {
+    let days: Vec<Value> = timings
+        .iter()
+        .map(|x| {
+            json!({
+                "day": x.day.into_inner(),
+                "total_ns": nanos(x.total()),
+                "parse": x.parse.as_ref().map(format_timing),
+                "part_1": x.part_1.as_ref().map(format_timing),
+                "part_2": x.part_2.as_ref().map(format_timing),
+            })
+        })
+        .collect();
+
+    let export = json!({
+        "environment": {
+            "rustc": environment.rustc,
+            "profile": environment.profile,
+            "alloc_tracking": environment.alloc_tracking,
+            "cpu": environment.cpu,
+            "cores": environment.cores,
+            "os": environment.os,
+            "arch": environment.arch,
+            "commit": environment.commit,
+            "dirty": environment.dirty,
+            "timestamp": environment.timestamp,
+            "stable": environment.stable,
+        },
+        "total_ns": nanos(timings.iter().map(Timings::total).sum()),
+        "days": days,
+    });
+
+    // serializing a `Value` can not fail.
+    serde_json::to_string_pretty(&export).unwrap_or_default() + "\n"
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Formats one row per step. The environment is repeated in every row, so that exports of
+/// several machines can be concatenated.
+#[must_use]
+pub fn format_csv(timings: &[Timings], environment: &Environment) -> String {
+    let optional = |x: Option<String>| x.unwrap_or_default();
+
+    let environment = [
+        optional(environment.rustc.clone()),
+        environment.profile.to_string(),
+        environment.alloc_tracking.to_string(),
+        optional(environment.cpu.clone()),
+        environment.cores.to_string(),
+        environment.os.to_string(),
+        environment.arch.to_string(),
+        optional(environment.commit.clone()),
+        optional(environment.dirty.map(|x| x.to_string())),
+        environment.timestamp.to_string(),
+        optional(environment.stable.clone()),
+    ]
+    .map(|x| csv_field(&x))
+    .join(",");
+
+    let mut s = String::from(
+        "day,step,timed_out,mean_ns,median_ns,stddev_ns,samples,allocs,alloc_bytes,peak_bytes,rustc,profile,alloc_tracking,cpu,cores,os,arch,commit,dirty,timestamp,stable\n",
+    );
+
+    for timings in timings {
+        let steps = [
+            ("parse", &timings.parse),
+            ("part_1", &timings.part_1),
+            ("part_2", &timings.part_2),
+        ];
+
+        for (step, timing) in steps {
+            let fields = match timing {
+                Some(Timing::Measured(x)) => [
+                    "false".into(),
+                    nanos(x.mean).to_string(),
+                    optional(x.median.map(|x| nanos(x).to_string())),
+                    optional(x.stddev.map(|x| nanos(x).to_string())),
+                    x.samples.to_string(),
+                    optional(x.allocs.map(|x| x.allocations.to_string())),
+                    optional(x.allocs.map(|x| x.bytes.to_string())),
+                    optional(x.allocs.map(|x| x.peak_bytes.to_string())),
+                ],
+                Some(Timing::TimedOut(_)) => {
+                    let mut fields: [String; 8] = Default::default();
+                    fields[0] = "true".into();
+                    fields
+                }
+                None => continue,
+            };
+
+            let _ = writeln!(
+                s,
+                "{},{step},{},{environment}",
+                timings.day.into_inner(),
+                fields.join(",")
+            );
+        }
+    }
+
+    s
+}
+
+/// Writes the timings to a `.json` or `.csv` file.
+pub fn export(
+    path: &Path,
+    format: Format,
+    timings: &[Timings],
+    environment: &Environment,
+) -> io::Result<()> {
+    let contents = match format {
+        Format::Json => format_json(timings, environment),
+        Format::Csv => format_csv(timings, environment),
+    };
+
+    fs::write(path, contents)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_csv, format_json, parse_cpu_model, Environment, Format};
+    use crate::day;
+    use crate::template::allocator::AllocStats;
+    use crate::template::readme_benchmarks::{Measurement, Timing, Timings};
+    use std::path::Path;
+    use std::time::Duration;
+
+    fn get_environment() -> Environment {
+        Environment {
+            rustc: Some("rustc 1.74.0".into()),
+            profile: "release",
+            alloc_tracking: false,
+            cpu: Some("Some CPU, 8 cores".into()),
+            cores: 8,
+            os: "linux",
+            arch: "x86_64",
+            commit: Some("abc123".into()),
+            dirty: Some(true),
+            timestamp: 1701388800,
+            stable: Some("core 3, nice -10, interleaved warm-up".into()),
+        }
+    }
+
+    fn get_timings() -> Vec<Timings> {
+        vec![Timings {
+            day: day!(1),
+            parse: None,
+            part_1: Some(Timing::Measured(Measurement {
+                mean: Duration::from_micros(10),
+                median: Some(Duration::from_micros(9)),
+                stddev: Some(Duration::from_nanos(500)),
+                samples: 100,
+                allocs: Some(AllocStats {
+                    allocations: 1,
+                    bytes: 4,
+                    peak_bytes: 4,
+                }),
+            })),
+            part_2: Some(Timing::TimedOut(Duration::from_secs(5))),
+        }]
+    }
+
+    #[test]
+    fn derives_format_from_extension() {
+        assert_eq!(
+            Format::from_path(Path::new("a/results.json")),
+            Some(Format::Json)
+        );
+        assert_eq!(
+            Format::from_path(Path::new("results.csv")),
+            Some(Format::Csv)
+        );
+        assert_eq!(Format::from_path(Path::new("results.txt")), None);
+        assert_eq!(Format::from_path(Path::new("results")), None);
+    }
+
+    #[test]
+    fn parses_cpu_model() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: GenuineIntel\nmodel name\t: Intel(R) Core(TM) i7\nprocessor\t: 1\n";
+        assert_eq!(
+            parse_cpu_model(cpuinfo).as_deref(),
+            Some("Intel(R) Core(TM) i7")
+        );
+        assert_eq!(parse_cpu_model("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn formats_json() {
+        let json: serde_json::Value =
+            serde_json::from_str(&format_json(&get_timings(), &get_environment())).unwrap();
+
+        assert_eq!(json["environment"]["commit"], "abc123");
+        assert_eq!(json["environment"]["dirty"], true);
+        assert_eq!(
+            json["environment"]["stable"],
+            "core 3, nice -10, interleaved warm-up"
+        );
+        assert_eq!(json["total_ns"], 10000);
+        assert_eq!(json["days"][0]["day"], 1);
+        assert_eq!(json["days"][0]["parse"], serde_json::Value::Null);
+        assert_eq!(json["days"][0]["part_1"]["median_ns"], 9000);
+        assert_eq!(json["days"][0]["part_1"]["samples"], 100);
+        assert_eq!(json["days"][0]["part_1"]["allocs"], 1);
+        assert_eq!(json["days"][0]["part_1"]["peak_bytes"], 4);
+        assert_eq!(json["days"][0]["part_2"]["timed_out"], true);
+        assert_eq!(json["days"][0]["part_2"]["timeout_ns"], 5_000_000_000_u64);
+    }
+
+    #[test]
+    fn formats_csv() {
+        let csv = format_csv(&get_timings(), &get_environment());
+        let lines: Vec<&str> = csv.lines().collect();
+
+        let environment = "rustc 1.74.0,release,false,\"Some CPU, 8 cores\",8,linux,x86_64,abc123,true,1701388800,\"core 3, nice -10, interleaved warm-up\"";
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("day,step,timed_out,mean_ns,"));
+        assert_eq!(
+            lines[1],
+            format!("1,part_1,false,10000,9000,500,100,1,4,4,{environment}")
+        );
+        assert_eq!(lines[2], format!("1,part_2,true,,,,,,,,{environment}"));
+    }
+}
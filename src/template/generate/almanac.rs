@@ -0,0 +1,80 @@
+use std::fmt::Write;
+
+use super::Rng;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// Source ranges of a map as `(start, length)`. Ranges do not overlap, but often touch.
+fn source_ranges(rng: &mut Rng, count: usize, max_value: u64) -> Vec<(u64, u64)> {
+    let max_length = (max_value / count.max(1) as u64).max(1);
+    let mut ranges = vec![];
+    let mut start = rng.below(max_length);
+
+    for _ in 0..count {
+        let length = rng.between(1, max_length);
+        ranges.push((start, length));
+        start += length;
+        if rng.chance(1, 2) {
+            start += rng.below(max_length);
+        }
+    }
+
+    ranges
+}
+
+/// A seed almanac (2023 day 5) with `seed_pairs` pairs of seed range start and length, and seven
+/// maps of 1 to `ranges_per_map` ranges each, in random order.
+///
+/// Numbers are roughly below `max_value`. Keep it small to hit range boundaries often: half of the
+/// seed ranges start at the first number in, the last number in or the first number after a range.
+pub fn almanac(rng: &mut Rng, seed_pairs: usize, ranges_per_map: usize, max_value: u64) -> String {
+    let maps: Vec<Vec<(u64, u64, u64)>> = MAPS
+        .iter()
+        .map(|_| {
+            let count = rng.between(1, ranges_per_map as u64) as usize;
+            let mut ranges: Vec<(u64, u64, u64)> = source_ranges(rng, count, max_value)
+                .into_iter()
+                .map(|(source, length)| (rng.below(max_value), source, length))
+                .collect();
+            rng.shuffle(&mut ranges);
+            ranges
+        })
+        .collect();
+
+    let boundaries: Vec<u64> = maps
+        .iter()
+        .flatten()
+        .flat_map(|&(_, source, length)| [source, source + length - 1, source + length])
+        .collect();
+
+    let seeds: Vec<String> = (0..seed_pairs)
+        .flat_map(|_| {
+            let start = if !boundaries.is_empty() && rng.chance(1, 2) {
+                *rng.choose(&boundaries)
+            } else {
+                rng.below(max_value)
+            };
+            [start, rng.between(1, (max_value / 4).max(1))]
+        })
+        .map(|n| n.to_string())
+        .collect();
+
+    let mut s = format!("seeds: {}\n", seeds.join(" "));
+
+    for (name, ranges) in MAPS.iter().zip(maps) {
+        let _ = write!(s, "\n{name} map:\n");
+        for (destination, source, length) in ranges {
+            let _ = writeln!(s, "{destination} {source} {length}");
+        }
+    }
+
+    s
+}
@@ -0,0 +1,32 @@
+use std::fmt::Write;
+
+use super::Rng;
+
+const LABELS: &[u8] = b"23456789TJQKA";
+
+/// Camel Cards hands with bids (2023 day 7), e.g. `32T3K 765`.
+///
+/// Hands are distinct and have bids in `1..=1000`. Each hand is drawn from a few labels only, so that
+/// pairs, full houses and jokers show up as often as high cards.
+pub fn camel_cards(rng: &mut Rng, hands: usize) -> String {
+    assert!(hands <= 10_000, "too many hands");
+
+    let mut seen: Vec<String> = vec![];
+    let mut s = String::new();
+
+    while seen.len() < hands {
+        let pool: Vec<u8> = (0..rng.between(1, 5))
+            .map(|_| *rng.choose(LABELS))
+            .collect();
+        let hand: String = (0..5).map(|_| char::from(*rng.choose(&pool))).collect();
+
+        if seen.contains(&hand) {
+            continue;
+        }
+
+        let _ = writeln!(s, "{hand} {}", rng.between(1, 1000));
+        seen.push(hand);
+    }
+
+    s
+}
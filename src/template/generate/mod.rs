@@ -0,0 +1,204 @@
+/// Random puzzle inputs for property tests.
+///
+/// [`check`] runs an optimized part and a straightforward reference implementation on many generated
+/// inputs and fails with the first input they disagree on. Inputs start small and grow with every case,
+/// so the first failure tends to be easy to read. Every case has its own seed, derived from its number:
+/// `AOC_CASE=17 cargo test --bin 05` re-runs only case 17, `AOC_CASES=100000` runs more cases.
+use std::env;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+
+mod almanac;
+mod camel_cards;
+mod pipe_loop;
+mod scratchcards;
+
+pub use almanac::almanac;
+pub use camel_cards::camel_cards;
+pub use pipe_loop::pipe_loop;
+pub use scratchcards::scratchcards;
+
+/// Number of cases run by a check, unless overridden with `AOC_CASES`.
+pub const DEFAULT_CASES: usize = 1000;
+
+/// The size passed to the generator of the last case. Sizes grow linearly from 1.
+pub const MAX_SIZE: usize = 50;
+
+/// Small deterministic random number generator (SplitMix64).
+///
+/// Not suitable for anything but tests: the same seed yields the same sequence on every platform,
+/// which keeps failing cases reproducible without pinning the version of an external crate.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`. The slight bias for large `n` does not matter for tests.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+        ((u128::from(self.next_u64()) * u128::from(n)) >> 64) as u64
+    }
+
+    /// A number in `min..=max`.
+    pub fn between(&mut self, min: u64, max: u64) -> u64 {
+        assert!(min <= max, "empty range");
+        min + self.below(max - min + 1)
+    }
+
+    /// `true` with a probability of `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    /// A random element of a non-empty slice.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+
+    /// `count` distinct numbers in `min..=max`, in random order.
+    pub fn distinct(&mut self, count: usize, min: u64, max: u64) -> Vec<u64> {
+        assert!(count as u64 <= max - min + 1, "not enough distinct numbers");
+        let mut numbers: Vec<u64> = vec![];
+        while numbers.len() < count {
+            let n = self.between(min, max);
+            if !numbers.contains(&n) {
+                numbers.push(n);
+            }
+        }
+        numbers
+    }
+}
+
+fn env_number(name: &str) -> Option<usize> {
+    let value = env::var(name).ok()?;
+    match value.parse() {
+        Ok(n) => Some(n),
+        Err(_) => panic!("{name} must be a number, got \"{value}\""),
+    }
+}
+
+/// The size of case `i` out of `cases`.
+fn size_for_case(i: usize, cases: usize) -> usize {
+    1 + i * (MAX_SIZE - 1) / cases.saturating_sub(1).max(1)
+}
+
+fn seed_for_case(i: usize) -> u64 {
+    // scramble the case number, so that neighbouring cases do not share a prefix of their sequence.
+    Rng::new(i as u64).next_u64()
+}
+
+fn run<T>(func: &impl Fn(&str) -> T, input: &str) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(|| func(input))).map_err(|e| {
+        e.downcast_ref::<String>()
+            .cloned()
+            .or_else(|| e.downcast_ref::<&str>().map(ToString::to_string))
+            .unwrap_or_else(|| "unknown panic".into())
+    })
+}
+
+/// Generates inputs with `generate(rng, size)` and panics on the first one for which `optimized`
+/// and `reference` disagree, or either of them panics.
+pub fn check<T: PartialEq + Debug>(
+    generate: impl Fn(&mut Rng, usize) -> String,
+    optimized: impl Fn(&str) -> T,
+    reference: impl Fn(&str) -> T,
+) {
+    let cases = env_number("AOC_CASES").unwrap_or(DEFAULT_CASES);
+
+    let selected: Vec<usize> = match env_number("AOC_CASE") {
+        Some(case) => vec![case],
+        None => (0..cases).collect(),
+    };
+
+    for i in selected {
+        let mut rng = Rng::new(seed_for_case(i));
+        let input = generate(&mut rng, size_for_case(i, cases));
+
+        let expected = run(&reference, &input);
+        let actual = run(&optimized, &input);
+
+        if expected.is_err() || actual != expected {
+            // the default panic hook already printed the message of a panicking implementation.
+            panic!(
+                "case {i} failed, re-run it with AOC_CASE={i}\n\
+                 --- input ---\n{input}\n-------------\n\
+                 optimized: {actual:?}\nreference: {expected:?}"
+            );
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, size_for_case, Rng, MAX_SIZE};
+
+    #[test]
+    fn rng_is_deterministic() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        let first: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        let second: Vec<u64> = (0..5).map(|_| b.next_u64()).collect();
+
+        assert_eq!(first, second);
+        assert_ne!(first[0], Rng::new(8).next_u64());
+    }
+
+    #[test]
+    fn rng_stays_in_range() {
+        let mut rng = Rng::new(1);
+
+        for _ in 0..1000 {
+            assert!((3..=5).contains(&rng.between(3, 5)));
+        }
+
+        let mut numbers = rng.distinct(10, 1, 10);
+        numbers.sort_unstable();
+        assert_eq!(numbers, (1..=10).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn sizes_grow() {
+        assert_eq!(size_for_case(0, 1000), 1);
+        assert_eq!(size_for_case(999, 1000), MAX_SIZE);
+        assert_eq!(size_for_case(0, 1), 1);
+    }
+
+    #[test]
+    fn passes_agreeing_implementations() {
+        check(
+            |rng, size| "x".repeat(rng.below(size as u64) as usize),
+            str::len,
+            |input| input.chars().count(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "re-run it with AOC_CASE=")]
+    fn reports_disagreement() {
+        check(
+            |rng, _| rng.between(0, 9).to_string(),
+            |input| input.parse::<u32>().unwrap() % 7,
+            |input| input.parse::<u32>().unwrap(),
+        );
+    }
+}
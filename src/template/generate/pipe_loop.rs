@@ -0,0 +1,222 @@
+use super::Rng;
+
+const NORTH: u8 = 1;
+const SOUTH: u8 = 2;
+const EAST: u8 = 4;
+const WEST: u8 = 8;
+
+/// The pipe with openings in the given directions.
+fn pipe(openings: u8) -> char {
+    match openings {
+        x if x == NORTH | SOUTH => '|',
+        x if x == EAST | WEST => '-',
+        x if x == NORTH | EAST => 'L',
+        x if x == NORTH | WEST => 'J',
+        x if x == SOUTH | WEST => '7',
+        x if x == SOUTH | EAST => 'F',
+        _ => unreachable!("a loop cell has exactly two openings"),
+    }
+}
+
+fn openings(pipe: char) -> u8 {
+    match pipe {
+        '|' => NORTH | SOUTH,
+        '-' => EAST | WEST,
+        'L' => NORTH | EAST,
+        'J' => NORTH | WEST,
+        '7' => SOUTH | WEST,
+        'F' => SOUTH | EAST,
+        _ => 0,
+    }
+}
+
+/// A random spanning tree of a `rows` x `cols` grid, as the edges to the right and below each node.
+fn spanning_tree(rng: &mut Rng, rows: usize, cols: usize) -> (Vec<Vec<bool>>, Vec<Vec<bool>>) {
+    let mut right = vec![vec![false; cols]; rows];
+    let mut down = vec![vec![false; cols]; rows];
+    let mut visited = vec![vec![false; cols]; rows];
+    let mut stack = vec![(0, 0)];
+    visited[0][0] = true;
+
+    while let Some(&(r, c)) = stack.last() {
+        let mut next = vec![];
+        if r > 0 && !visited[r - 1][c] {
+            next.push((r - 1, c));
+        }
+        if r + 1 < rows && !visited[r + 1][c] {
+            next.push((r + 1, c));
+        }
+        if c > 0 && !visited[r][c - 1] {
+            next.push((r, c - 1));
+        }
+        if c + 1 < cols && !visited[r][c + 1] {
+            next.push((r, c + 1));
+        }
+
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let (nr, nc) = *rng.choose(&next);
+        match (nr.cmp(&r), nc.cmp(&c)) {
+            (std::cmp::Ordering::Less, _) => down[nr][nc] = true,
+            (std::cmp::Ordering::Greater, _) => down[r][c] = true,
+            (_, std::cmp::Ordering::Less) => right[nr][nc] = true,
+            _ => right[r][c] = true,
+        }
+        visited[nr][nc] = true;
+        stack.push((nr, nc));
+    }
+
+    (right, down)
+}
+
+/// The openings of a loop that fills a `2 * rows` x `2 * cols` grid.
+///
+/// Every node of a spanning tree becomes a 2x2 block of pipes forming a tiny loop. For every tree
+/// edge, the loops of the two blocks are joined, so all blocks end up on a single loop around the tree.
+fn loop_openings(rng: &mut Rng, rows: usize, cols: usize) -> Vec<Vec<u8>> {
+    let (right, down) = spanning_tree(rng, rows, cols);
+    let mut grid = vec![vec![0; 2 * cols]; 2 * rows];
+
+    for r in 0..rows {
+        for c in 0..cols {
+            let (top, left) = (2 * r, 2 * c);
+            grid[top][left] = SOUTH | EAST;
+            grid[top][left + 1] = SOUTH | WEST;
+            grid[top + 1][left] = NORTH | EAST;
+            grid[top + 1][left + 1] = NORTH | WEST;
+        }
+    }
+
+    for r in 0..rows {
+        for c in 0..cols {
+            let (top, left) = (2 * r, 2 * c);
+            if right[r][c] {
+                grid[top][left + 1] ^= SOUTH | EAST;
+                grid[top + 1][left + 1] ^= NORTH | EAST;
+                grid[top][left + 2] ^= SOUTH | WEST;
+                grid[top + 1][left + 2] ^= NORTH | WEST;
+            }
+            if down[r][c] {
+                grid[top + 1][left] ^= EAST | SOUTH;
+                grid[top + 1][left + 1] ^= WEST | SOUTH;
+                grid[top + 2][left] ^= EAST | NORTH;
+                grid[top + 2][left + 1] ^= WEST | NORTH;
+            }
+        }
+    }
+
+    grid
+}
+
+/// A pipe maze (2023 day 10) of `rows` x `cols` tiles, which may differ.
+///
+/// The main loop has `4 * h * w` tiles for a random `h <= rows / 2` and `w <= cols / 2`, so the
+/// farthest tile is `2 * h * w` steps from the start `S`. The remaining tiles are ground or junk pipes,
+/// which may touch the loop, but only the two neighbours on the loop connect to `S`.
+pub fn pipe_loop(rng: &mut Rng, rows: usize, cols: usize) -> String {
+    assert!(rows >= 2 && cols >= 2, "the grid is too small for a loop");
+
+    let h = rng.between(1, rows as u64 / 2) as usize;
+    let w = rng.between(1, cols as u64 / 2) as usize;
+    let offset = (
+        rng.between(0, (rows - 2 * h) as u64) as usize,
+        rng.between(0, (cols - 2 * w) as u64) as usize,
+    );
+
+    let mut grid: Vec<Vec<char>> = (0..rows)
+        .map(|_| {
+            (0..cols)
+                .map(|_| *rng.choose(&['.', '.', '|', '-', 'L', 'J', '7', 'F']))
+                .collect()
+        })
+        .collect();
+
+    for (r, row) in loop_openings(rng, h, w).into_iter().enumerate() {
+        for (c, openings) in row.into_iter().enumerate() {
+            grid[offset.0 + r][offset.1 + c] = pipe(openings);
+        }
+    }
+
+    let start = (
+        offset.0 + rng.below(2 * h as u64) as usize,
+        offset.1 + rng.below(2 * w as u64) as usize,
+    );
+    grid[start.0][start.1] = 'S';
+
+    // junk pipes next to the start must not point at it.
+    let neighbours = [
+        (start.0.checked_sub(1), Some(start.1), SOUTH),
+        (Some(start.0 + 1), Some(start.1), NORTH),
+        (Some(start.0), start.1.checked_sub(1), EAST),
+        (Some(start.0), Some(start.1 + 1), WEST),
+    ];
+    for (r, c, towards_start) in neighbours {
+        let (Some(r), Some(c)) = (r, c) else {
+            continue;
+        };
+        let is_loop =
+            (offset.0..offset.0 + 2 * h).contains(&r) && (offset.1..offset.1 + 2 * w).contains(&c);
+        if r < rows && c < cols && !is_loop && openings(grid[r][c]) & towards_start != 0 {
+            grid[r][c] = '.';
+        }
+    }
+
+    let lines: Vec<String> = grid.into_iter().map(String::from_iter).collect();
+    lines.join("\n") + "\n"
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{openings, pipe_loop, EAST, NORTH, SOUTH, WEST};
+    use crate::template::generate::Rng;
+
+    #[test]
+    fn generates_single_loop() {
+        let mut rng = Rng::new(3);
+
+        for (rows, cols) in [(2, 2), (5, 9), (12, 4)] {
+            let input = pipe_loop(&mut rng, rows, cols);
+            let grid: Vec<Vec<char>> = input.lines().map(|x| x.chars().collect()).collect();
+            assert_eq!((grid.len(), grid[0].len()), (rows, cols));
+
+            let start = (0..rows)
+                .flat_map(|r| (0..cols).map(move |c| (r, c)))
+                .find(|&(r, c)| grid[r][c] == 'S')
+                .unwrap();
+
+            // walk the loop from the start, it must return to the start.
+            let step = |(r, c): (usize, usize), dir: u8| match dir {
+                NORTH => (r.wrapping_sub(1), c),
+                SOUTH => (r + 1, c),
+                EAST => (r, c + 1),
+                _ => (r, c.wrapping_sub(1)),
+            };
+            let reverse = |dir: u8| match dir {
+                NORTH => SOUTH,
+                SOUTH => NORTH,
+                EAST => WEST,
+                _ => EAST,
+            };
+            let tile = |(r, c): (usize, usize)| grid.get(r).and_then(|x| x.get(c)).copied();
+
+            let connected: Vec<u8> = [NORTH, SOUTH, EAST, WEST]
+                .into_iter()
+                .filter(|&dir| {
+                    tile(step(start, dir)).is_some_and(|x| openings(x) & reverse(dir) != 0)
+                })
+                .collect();
+            assert_eq!(connected.len(), 2);
+
+            let (mut position, mut dir, mut length) = (step(start, connected[0]), connected[0], 1);
+            while position != start {
+                dir = openings(tile(position).unwrap()) & !reverse(dir);
+                position = step(position, dir);
+                length += 1;
+            }
+            assert_eq!(length % 4, 0);
+        }
+    }
+}
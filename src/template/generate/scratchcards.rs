@@ -0,0 +1,44 @@
+use std::fmt::Write;
+
+use super::Rng;
+
+/// Formats numbers right-aligned in columns of two, like `41 48  6 31`.
+fn format_numbers(numbers: &[u64]) -> String {
+    let numbers: Vec<String> = numbers.iter().map(|n| format!("{n:>2}")).collect();
+    numbers.join(" ")
+}
+
+/// Scratchcards (2023 day 4), e.g. `Card   1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53`.
+///
+/// Every card has `winning` distinct winning numbers and `numbers` distinct numbers you have, all in
+/// `1..=99`. Cards near the end never win copies of cards past the end of the table.
+pub fn scratchcards(rng: &mut Rng, cards: usize, winning: usize, numbers: usize) -> String {
+    assert!(winning + numbers <= 99, "too many numbers per card");
+    let mut s = String::new();
+
+    for i in 1..=cards {
+        let winning_numbers = rng.distinct(winning, 1, 99);
+
+        // pick the number of matches first, otherwise almost every card would lose.
+        let max_matches = winning.min(numbers).min(cards - i);
+        let matches = rng.below(max_matches as u64 + 1) as usize;
+
+        let mut own: Vec<u64> = winning_numbers[..matches].to_vec();
+        while own.len() < numbers {
+            let n = rng.between(1, 99);
+            if !own.contains(&n) && !winning_numbers.contains(&n) {
+                own.push(n);
+            }
+        }
+        rng.shuffle(&mut own);
+
+        let _ = writeln!(
+            s,
+            "Card {i:>3}: {} | {}",
+            format_numbers(&winning_numbers),
+            format_numbers(&own)
+        );
+    }
+
+    s
+}
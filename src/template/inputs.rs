@@ -0,0 +1,282 @@
+/// Named inputs: puzzle inputs of several people for one day, in `data/inputs/DD/NAME.txt`.
+///
+/// Known answers are listed in `data/inputs/DD/answers.toml`, keyed by the name of the input.
+/// A part without a known answer is run, but not checked.
+///
+/// ```toml
+/// [alice]
+/// part_one = 54632
+/// part_two = 54019
+///
+/// [bob]
+/// part_one = 55123
+/// ```
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{env, fs, io};
+
+use crate::template::config::config;
+use crate::template::{encryption, examples, table};
+use crate::Day;
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "invalid answers for named inputs: {e}"),
+            Error::IO(e) => write!(f, "could not read named inputs: {e}"),
+        }
+    }
+}
+
+/// A named input and the answers it is known to produce.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedInput {
+    pub name: String,
+    pub path: PathBuf,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+/// The known answers of a named input, `(part_one, part_two)`.
+pub type KnownAnswers = BTreeMap<String, (Option<String>, Option<String>)>;
+
+/// The directory of named inputs of a day, e.g. `data/inputs/01`.
+#[must_use]
+pub fn get_inputs_dir(day: Day) -> PathBuf {
+    config().inputs_dir().join(day.to_string())
+}
+
+/// Parses the contents of an answers file.
+pub fn parse_answers(s: &str) -> Result<KnownAnswers, Error> {
+    let table: toml::Table = s
+        .parse()
+        .map_err(|e: toml::de::Error| Error::Parser(e.to_string()))?;
+
+    table
+        .into_iter()
+        .map(|(name, answers)| {
+            let answers = answers
+                .as_table()
+                .ok_or_else(|| Error::Parser(format!("`{name}` must be a table")))?;
+
+            let answer = |key: &str| {
+                answers
+                    .get(key)
+                    .map(examples::answer_to_string)
+                    .transpose()
+                    .map_err(|e| Error::Parser(format!("`{name}.{key}`: {e}")))
+            };
+
+            Ok((name.clone(), (answer("part_one")?, answer("part_two")?)))
+        })
+        .collect()
+}
+
+/// Reads the named inputs of a day, sorted by name. Returns no inputs if the day has none.
+/// Encrypted inputs, `NAME.txt.enc`, are included.
+pub fn read_inputs(day: Day) -> Result<Vec<NamedInput>, Error> {
+    let dir = env::current_dir()?.join(get_inputs_dir(day));
+
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let answers_path = dir.join(ANSWERS_FILE);
+    let mut answers = if answers_path.exists() {
+        parse_answers(&fs::read_to_string(answers_path)?)?
+    } else {
+        KnownAnswers::new()
+    };
+
+    let mut inputs: Vec<NamedInput> = vec![];
+
+    for entry in fs::read_dir(&dir)? {
+        let file_name = entry?.file_name().to_string_lossy().to_string();
+
+        // encrypted inputs are listed by the path of their plain text, see `encryption::read_input_file`.
+        let Some(name) = file_name
+            .strip_suffix(&format!(".txt.{}", encryption::EXTENSION))
+            .or_else(|| file_name.strip_suffix(".txt"))
+        else {
+            continue;
+        };
+
+        if inputs.iter().any(|x| x.name == name) {
+            continue;
+        }
+
+        let (part_one, part_two) = answers.remove(name).unwrap_or_default();
+
+        inputs.push(NamedInput {
+            name: name.to_string(),
+            path: dir.join(format!("{name}.txt")),
+            part_one,
+            part_two,
+        });
+    }
+
+    // answers without an input are most likely a typo in the name.
+    if let Some(name) = answers.keys().next() {
+        return Err(Error::Parser(format!(
+            "found answers for `{name}`, but no input file `{name}.txt`"
+        )));
+    }
+
+    inputs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(inputs)
+}
+
+/// How a part did on a named input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The part returned an answer, or `None`, in the given time.
+    Answered {
+        answer: Option<String>,
+        expected: Option<String>,
+        duration: Duration,
+    },
+    TimedOut,
+    Panic,
+}
+
+impl Outcome {
+    #[must_use]
+    pub fn is_failure(&self) -> bool {
+        match self {
+            Outcome::Answered {
+                answer, expected, ..
+            } => expected.is_some() && answer != expected,
+            Outcome::TimedOut | Outcome::Panic => true,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Answered {
+                answer,
+                expected,
+                duration,
+            } => {
+                let actual = answer.as_deref().unwrap_or("None");
+                match expected {
+                    None => write!(f, "? {actual} ({duration:.1?})"),
+                    Some(x) if answer.as_ref() == Some(x) => {
+                        write!(f, "✔ {actual} ({duration:.1?})")
+                    }
+                    Some(x) => write!(f, "✖ {actual}, expected {x} ({duration:.1?})"),
+                }
+            }
+            Outcome::TimedOut => write!(f, "✖ TIMEOUT"),
+            Outcome::Panic => write!(f, "✖ panicked"),
+        }
+    }
+}
+
+/// The outcomes of both parts on a named input.
+#[derive(Debug, Clone)]
+pub struct InputResult {
+    pub name: String,
+    pub part_one: Outcome,
+    pub part_two: Outcome,
+}
+
+/// Formats the results as a table with one row per input and one column per part.
+#[must_use]
+pub fn format_matrix(results: &[InputResult]) -> String {
+    let rows: Vec<[String; 3]> = results
+        .iter()
+        .map(|x| {
+            [
+                x.name.clone(),
+                x.part_one.to_string(),
+                x.part_two.to_string(),
+            ]
+        })
+        .collect();
+
+    table::format_columns(["Input", "Part 1", "Part 2"], &rows, " | ")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_matrix, parse_answers, InputResult, Outcome};
+    use std::time::Duration;
+
+    #[test]
+    fn parses_answers() {
+        let answers = parse_answers(
+            r#"
+            [alice]
+            part_one = 142
+            part_two = "ABC"
+
+            [bob]
+            part_two = 281
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            answers.get("alice"),
+            Some(&(Some("142".into()), Some("ABC".into())))
+        );
+        assert_eq!(answers.get("bob"), Some(&(None, Some("281".into()))));
+        assert!(parse_answers("[carol]\npart_one = 1.5").is_err());
+        assert!(parse_answers("carol = 1").is_err());
+    }
+
+    #[test]
+    fn formats_matrix() {
+        let answered = |answer: &str, expected: Option<&str>| Outcome::Answered {
+            answer: Some(answer.into()),
+            expected: expected.map(Into::into),
+            duration: Duration::from_micros(12),
+        };
+
+        let results = [
+            InputResult {
+                name: "alice".into(),
+                part_one: answered("142", Some("142")),
+                part_two: answered("281", None),
+            },
+            InputResult {
+                name: "bob".into(),
+                part_one: answered("140", Some("141")),
+                part_two: Outcome::TimedOut,
+            },
+        ];
+
+        assert!(!results[0].part_one.is_failure());
+        assert!(!results[0].part_two.is_failure());
+        assert!(results[1].part_one.is_failure());
+
+        let matrix = format_matrix(&results);
+        let lines: Vec<&str> = matrix.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "Input | Part 1                       | Part 2",
+                "alice | ✔ 142 (12.0µs)               | ? 281 (12.0µs)",
+                "bob   | ✖ 140, expected 141 (12.0µs) | ✖ TIMEOUT",
+            ]
+        );
+    }
+}
@@ -0,0 +1,453 @@
+/// Statistics about a puzzle input, see the `inspect` command.
+///
+/// Helps to pick types and algorithms before writing a solution: the shape of lines and blocks,
+/// whether the input is a grid, which characters occur, and the range of the integers in it.
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// Number of characters, block sizes and grids that are listed before the rest is summarized.
+const MAX_LISTED: usize = 16;
+
+/// A run of lines between blank lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub lines: usize,
+    /// `(rows, columns)` if all lines have the same length and no whitespace.
+    pub grid: Option<(usize, usize)>,
+}
+
+/// The integers in an input. A `-` is read as a sign unless it follows a letter or a digit,
+/// so that ranges like `1-3` are not read as negative numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Integers {
+    pub count: usize,
+    pub negative: usize,
+    /// `None` if no integer fits into an `i128`.
+    pub min: Option<i128>,
+    pub max: Option<i128>,
+    /// Most digits of an integer, without its sign.
+    pub width: usize,
+    /// Integers that do not fit into an `i128`, they are not part of `min` and `max`.
+    pub too_large: usize,
+}
+
+impl Integers {
+    /// Warnings about types that are too small for the integers.
+    #[must_use]
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = vec![];
+
+        if self.too_large > 0 {
+            warnings.push(format!(
+                "{} integer(s) exceed i128, parse them as strings or big integers",
+                self.too_large
+            ));
+        }
+
+        if let Some(warning) = self.type_warning() {
+            warnings.push(warning);
+        }
+
+        if self.negative > 0 {
+            warnings.push(format!(
+                "{} negative integer(s), unsigned types do not fit",
+                self.negative
+            ));
+        }
+
+        warnings
+    }
+
+    /// A warning if the integers that fit into an `i128` do not fit into an `u32` or `i32`.
+    fn type_warning(&self) -> Option<String> {
+        let (smallest, largest) = (self.min?, self.max?);
+        let range = format!("min {smallest}, max {largest}");
+        let fits = |min: i128, max: i128| smallest >= min && largest <= max;
+
+        if !fits(i128::from(i64::MIN), i128::from(i64::MAX)) {
+            Some(format!(
+                "integers exceed i64 ({range}), use {}",
+                Self::smallest_type(fits)
+            ))
+        } else if !fits(0, i128::from(u32::MAX)) && !fits(i32::MIN.into(), i32::MAX.into()) {
+            Some(format!(
+                "integers exceed u32 ({range}), use {}",
+                Self::smallest_type(fits)
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// The smallest integer type that holds all values, given whether a range fits.
+    fn smallest_type(fits: impl Fn(i128, i128) -> bool) -> &'static str {
+        [
+            ("u32", 0, i128::from(u32::MAX)),
+            ("i32", i32::MIN.into(), i32::MAX.into()),
+            ("u64", 0, i128::from(u64::MAX)),
+            ("i64", i64::MIN.into(), i64::MAX.into()),
+            ("u128", 0, i128::MAX),
+        ]
+        .iter()
+        .find(|(_, min, max)| fits(*min, *max))
+        .map_or("i128", |(name, _, _)| name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub lines: usize,
+    pub blank_lines: usize,
+    /// Number of non-blank lines by length in characters.
+    pub line_lengths: BTreeMap<usize, usize>,
+    pub blocks: Vec<Block>,
+    /// Characters other than line breaks by count, most frequent first.
+    pub chars: Vec<(char, usize)>,
+    /// `None` if the input has no integers.
+    pub integers: Option<Integers>,
+}
+
+#[must_use]
+pub fn inspect(input: &str) -> Report {
+    let lines: Vec<&str> = input.lines().collect();
+
+    let mut line_lengths = BTreeMap::new();
+    for line in lines.iter().filter(|x| !x.trim().is_empty()) {
+        *line_lengths.entry(line.chars().count()).or_insert(0) += 1;
+    }
+
+    let mut chars: BTreeMap<char, usize> = BTreeMap::new();
+    for c in input.chars().filter(|&x| x != '\n') {
+        *chars.entry(c).or_insert(0) += 1;
+    }
+    let mut chars: Vec<(char, usize)> = chars.into_iter().collect();
+    chars.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    Report {
+        lines: lines.len(),
+        blank_lines: lines.iter().filter(|x| x.trim().is_empty()).count(),
+        line_lengths,
+        blocks: find_blocks(&lines),
+        chars,
+        integers: find_integers(input),
+    }
+}
+
+fn find_blocks(lines: &[&str]) -> Vec<Block> {
+    lines
+        .split(|x| x.trim().is_empty())
+        .filter(|x| !x.is_empty())
+        .map(|block| {
+            let columns = block[0].chars().count();
+            let is_grid = block.len() > 1
+                && columns > 1
+                && block.iter().all(|line| {
+                    line.chars().count() == columns && !line.contains(char::is_whitespace)
+                });
+
+            Block {
+                lines: block.len(),
+                grid: is_grid.then_some((block.len(), columns)),
+            }
+        })
+        .collect()
+}
+
+fn find_integers(input: &str) -> Option<Integers> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut integers: Option<Integers> = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        if !chars[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < chars.len() && chars[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        let negative = start > 0
+            && chars[start - 1] == '-'
+            && (start < 2 || !chars[start - 2].is_alphanumeric());
+        let digits: String = chars[start..i].iter().collect();
+
+        let x = integers.get_or_insert(Integers {
+            count: 0,
+            negative: 0,
+            min: None,
+            max: None,
+            width: 0,
+            too_large: 0,
+        });
+
+        x.count += 1;
+        x.width = x.width.max(digits.len());
+
+        if negative {
+            x.negative += 1;
+        }
+
+        match digits.parse::<i128>() {
+            Ok(value) => {
+                let value = if negative { -value } else { value };
+                x.min = Some(x.min.map_or(value, |min| min.min(value)));
+                x.max = Some(x.max.map_or(value, |max| max.max(value)));
+            }
+            Err(_) => x.too_large += 1,
+        }
+    }
+
+    integers
+}
+
+/// Summarizes a list, e.g. `1, 2, 3 and 5 more`.
+fn format_list(items: &[String]) -> String {
+    let mut s = items
+        .iter()
+        .take(MAX_LISTED)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if items.len() > MAX_LISTED {
+        let _ = write!(s, " and {} more", items.len() - MAX_LISTED);
+    }
+
+    s
+}
+
+fn format_line_lengths(line_lengths: &BTreeMap<usize, usize>) -> String {
+    let total: usize = line_lengths.values().sum();
+
+    match line_lengths.len() {
+        0 => "-".into(),
+        1 => format!("{} (all lines)", line_lengths.keys().next().unwrap()),
+        2..=5 => line_lengths
+            .iter()
+            .map(|(length, count)| format!("{length} ({count} line(s))"))
+            .collect::<Vec<_>>()
+            .join(", "),
+        n => {
+            // the length at which half of the lines are shorter or equal.
+            let mut seen = 0;
+            let median = line_lengths
+                .iter()
+                .find(|(_, &count)| {
+                    seen += count;
+                    2 * seen >= total
+                })
+                .map(|(length, _)| *length)
+                .unwrap_or_default();
+
+            format!(
+                "{} to {}, median {median}, {n} distinct lengths",
+                line_lengths.keys().next().unwrap(),
+                line_lengths.keys().last().unwrap(),
+            )
+        }
+    }
+}
+
+/// Formats the report, one statistic per line, followed by warnings.
+#[must_use]
+pub fn format_report(report: &Report) -> String {
+    let mut rows: Vec<(&str, String)> = vec![
+        (
+            "Lines",
+            format!("{} ({} blank)", report.lines, report.blank_lines),
+        ),
+        ("Line length", format_line_lengths(&report.line_lengths)),
+    ];
+
+    let block_sizes: Vec<String> = report.blocks.iter().map(|x| x.lines.to_string()).collect();
+    rows.push((
+        "Blocks",
+        match report.blocks.len() {
+            0 | 1 => report.blocks.len().to_string(),
+            n => format!("{n}, with {} line(s)", format_list(&block_sizes)),
+        },
+    ));
+
+    let grids: Vec<String> = report
+        .blocks
+        .iter()
+        .filter_map(|x| x.grid)
+        .map(|(rows, columns)| format!("{rows}x{columns}"))
+        .collect();
+    rows.push((
+        "Grid",
+        match (report.blocks.len(), grids.len()) {
+            (_, 0) => "-".into(),
+            (1, _) => {
+                let (rows, columns) = report.blocks[0].grid.unwrap();
+                format!("{rows} rows x {columns} columns")
+            }
+            (n, _) => format!(
+                "{} of {n} blocks are grids, {}",
+                grids.len(),
+                format_list(&grids)
+            ),
+        },
+    ));
+
+    let chars: Vec<String> = report
+        .chars
+        .iter()
+        .map(|(c, count)| format!("{c:?} {count}"))
+        .collect();
+    rows.push((
+        "Characters",
+        match chars.len() {
+            0 => "-".into(),
+            _ => format_list(&chars),
+        },
+    ));
+
+    rows.push((
+        "Integers",
+        match &report.integers {
+            None => "-".into(),
+            Some(x) => match (x.min, x.max) {
+                (Some(min), Some(max)) => format!(
+                    "{}, min {min}, max {max}, up to {} digits",
+                    x.count, x.width
+                ),
+                // none of the integers fit into an `i128`.
+                _ => format!("{}, up to {} digits", x.count, x.width),
+            },
+        },
+    ));
+
+    let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0) + 1;
+    let mut s = String::new();
+
+    for (name, value) in rows {
+        let _ = writeln!(
+            s,
+            "{ANSI_BOLD}{:<width$}{ANSI_RESET} {value}",
+            format!("{name}:")
+        );
+    }
+
+    let warnings = report
+        .integers
+        .as_ref()
+        .map(Integers::warnings)
+        .unwrap_or_default();
+
+    for warning in warnings {
+        let _ = writeln!(s, "⚠️ {warning}");
+    }
+
+    s
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_line_lengths, format_report, inspect, Block};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn inspects_grids() {
+        let report = inspect("#.#\n..#\n###\n");
+
+        assert_eq!(report.lines, 3);
+        assert_eq!(report.blank_lines, 0);
+        assert_eq!(report.line_lengths, BTreeMap::from([(3, 3)]));
+        assert_eq!(
+            report.blocks,
+            vec![Block {
+                lines: 3,
+                grid: Some((3, 3))
+            }]
+        );
+        assert_eq!(report.chars, vec![('#', 6), ('.', 3)]);
+        assert_eq!(report.integers, None);
+    }
+
+    #[test]
+    fn inspects_blocks() {
+        let report = inspect("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 48\n");
+
+        assert_eq!(report.blank_lines, 1);
+        assert_eq!(
+            report.blocks,
+            vec![
+                Block {
+                    lines: 1,
+                    grid: None
+                },
+                Block {
+                    lines: 3,
+                    grid: None
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_integers() {
+        let integers = inspect("x=-3, y=12\n1-3 a: 4294967296\n").integers.unwrap();
+
+        assert_eq!(integers.count, 5);
+        assert_eq!(integers.negative, 1);
+        assert_eq!(integers.min, Some(-3));
+        assert_eq!(integers.max, Some(4_294_967_296));
+        assert_eq!(integers.width, 10);
+
+        let warnings = integers.warnings();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("exceed u32 (min -3, max 4294967296), use i64"));
+        assert!(warnings[1].contains("1 negative"));
+    }
+
+    #[test]
+    fn warns_about_large_integers() {
+        let warnings = |input: &str| inspect(input).integers.unwrap().warnings();
+
+        assert!(warnings("1 4294967295").is_empty());
+        assert!(warnings("-2147483648 2147483647")[0].contains("1 negative"));
+        assert!(
+            warnings("-1 2147483648")[0].contains("exceed u32 (min -1, max 2147483648), use i64")
+        );
+        assert!(warnings("9223372036854775808")[0]
+            .contains("exceed i64 (min 9223372036854775808, max 9223372036854775808), use u64"));
+        assert!(warnings("18446744073709551616")[0].contains("use u128"));
+        assert!(warnings("-1 9223372036854775808")[0].contains("use i128"));
+        assert!(warnings(&"9".repeat(40))[0].contains("1 integer(s) exceed i128"));
+    }
+
+    #[test]
+    fn omits_range_without_i128_integers() {
+        let report = inspect(&format!("{0} -{0}\n", "9".repeat(40)));
+        let integers = report.integers.as_ref().unwrap();
+
+        assert_eq!(integers.too_large, 2);
+        assert_eq!((integers.min, integers.max), (None, None));
+        assert_eq!(
+            integers.warnings(),
+            vec![
+                "2 integer(s) exceed i128, parse them as strings or big integers".to_string(),
+                "1 negative integer(s), unsigned types do not fit".to_string(),
+            ]
+        );
+        assert!(format_report(&report).contains("2, up to 40 digits\n"));
+    }
+
+    #[test]
+    fn formats_line_lengths() {
+        assert_eq!(
+            format_line_lengths(&BTreeMap::from([(3, 2), (5, 1)])),
+            "3 (2 line(s)), 5 (1 line(s))"
+        );
+        assert_eq!(
+            format_line_lengths(&(1..=10).map(|x| (x, 1)).collect()),
+            "1 to 10, median 5, 10 distinct lengths"
+        );
+    }
+}
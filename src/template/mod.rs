@@ -1,48 +1,262 @@
-use crate::Day;
-use std::{env, fs};
+use crate::{Day, Part};
+use std::env;
 
+pub mod allocator;
+pub mod answers;
 pub mod aoc_cli;
+pub mod bench;
+pub mod cli;
 pub mod commands;
+pub mod compare;
+pub mod config;
+pub mod crosscheck;
+pub mod encryption;
+pub mod examples;
+pub mod export;
+pub mod generate;
+pub mod inputs;
+pub mod inspect;
+pub mod profiler;
+pub mod puzzle;
+pub mod readme;
 pub mod readme_benchmarks;
+pub mod readme_stars;
+pub mod render;
 pub mod runner;
+pub mod stable;
+pub mod table;
+pub mod templates;
+pub mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
+pub const ANSI_DIM: &str = "\x1b[2m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Decrypts the file if only its encrypted form exists, see [`encryption`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let filepath = cwd
+        .join(&config::config().data_dir)
+        .join(folder)
+        .join(format!("{day}.txt"));
+    let f = encryption::read_input_file(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file: {e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, day: Day, part: Part) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(&config::config().data_dir)
         .join(folder)
         .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let f = encryption::read_input_file(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file: {e}"))
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// If a `parse` function is passed as second argument, the input is parsed once and a reference to the
+/// parsed value is passed to both parts. Parsing is timed separately from the parts.
+///
+/// Alternative implementations of a part can be registered as named variants after a semicolon.
+/// They have the same signature as the part, `--variant NAME` runs them instead of the default
+/// implementation and `--crosscheck` compares the answers of all variants:
+///
+/// ```ignore
+/// advent_of_code::solution!(7; part_one: [naive = part_one_naive], part_two: [naive = part_two_naive]);
+/// advent_of_code::solution!(5, parse; part_two: [ranges = part_two_ranges]);
+/// ```
+///
+/// Also generates a `test_examples` test that checks the parts against the day's example manifest,
+/// and a `register` function that passes the parts to a benchmark harness, see [`bench`].
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    // selects the variant of a part, falling back to the default implementation.
+    (@select $variant:ident, $default:path, [$($name:ident = $func:path),*]) => {{
+        #[allow(unused_mut)]
+        let mut func = $default as fn(_) -> _;
+        $(
+            if $variant == Some(stringify!($name)) {
+                func = $func;
+            }
+        )*
+        func
+    }};
+    (@solution $day:expr, [$($n1:ident = $f1:path),*], [$($n2:ident = $f2:path),*]) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
         fn main() {
+            use advent_of_code::template::crosscheck::{variant, DEFAULT};
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+
+            let selected = solution_args().variant.as_deref();
+            check_variant(selected, &[$(stringify!($n1)),*], &[$(stringify!($n2)),*]);
+
+            if solution_args().examples {
+                check_examples(DAY, part_one, part_two);
+                return;
+            }
+
+            if solution_args().crosscheck {
+                run_crosscheck(
+                    DAY,
+                    &[variant(DEFAULT, part_one) $(, variant(stringify!($n1), $f1))*],
+                    &[variant(DEFAULT, part_two) $(, variant(stringify!($n2), $f2))*],
+                );
+                return;
+            }
+
+            let part_one = advent_of_code::solution!(@select selected, part_one, [$($n1 = $f1),*]);
+            let part_two = advent_of_code::solution!(@select selected, part_two, [$($n2 = $f2),*]);
+
+            if solution_args().all_inputs {
+                run_all_inputs(DAY, |input| {
+                    (
+                        run_part(part_one, input, DAY, advent_of_code::Part::One),
+                        run_part(part_two, input, DAY, advent_of_code::Part::Two),
+                    )
+                });
+                return;
+            }
+
+            // parts run on worker threads that may outlive `main` if they time out.
+            let input: &'static str = advent_of_code::template::read_file("inputs", DAY).leak();
+            run_part(part_one, input, DAY, advent_of_code::Part::One);
+            run_part(part_two, input, DAY, advent_of_code::Part::Two);
         }
+
+        /// Passes the parts and their variants to a benchmark harness.
+        #[allow(dead_code)]
+        pub fn register(registry: &mut impl advent_of_code::template::bench::Registry) {
+            let Some(input) = advent_of_code::template::bench::read_input(DAY) else {
+                registry.skip(DAY, "input file is missing");
+                return;
+            };
+
+            registry.bench(DAY, "part_one", input.as_str(), part_one);
+            $(registry.bench(DAY, concat!("part_one/", stringify!($n1)), input.as_str(), $f1);)*
+            registry.bench(DAY, "part_two", input.as_str(), part_two);
+            $(registry.bench(DAY, concat!("part_two/", stringify!($n2)), input.as_str(), $f2);)*
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn test_examples() {
+            advent_of_code::template::examples::assert_examples(DAY, part_one, part_two);
+        }
+    };
+    (@solution $day:expr, $parse:path, [$($n1:ident = $f1:path),*], [$($n2:ident = $f2:path),*]) => {
+        /// The current day.
+        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+
+        fn main() {
+            use advent_of_code::template::crosscheck::{variant, DEFAULT};
+            use advent_of_code::template::runner::*;
+
+            let selected = solution_args().variant.as_deref();
+            check_variant(selected, &[$(stringify!($n1)),*], &[$(stringify!($n2)),*]);
+
+            if solution_args().examples {
+                check_examples(
+                    DAY,
+                    |input: &str| part_one(&$parse(input)),
+                    |input: &str| part_two(&$parse(input)),
+                );
+                return;
+            }
+
+            if solution_args().crosscheck {
+                run_crosscheck(
+                    DAY,
+                    &[
+                        variant(DEFAULT, |input: &str| part_one(&$parse(input)))
+                        $(, variant(stringify!($n1), |input: &str| $f1(&$parse(input))))*
+                    ],
+                    &[
+                        variant(DEFAULT, |input: &str| part_two(&$parse(input)))
+                        $(, variant(stringify!($n2), |input: &str| $f2(&$parse(input))))*
+                    ],
+                );
+                return;
+            }
+
+            let part_one = advent_of_code::solution!(@select selected, part_one, [$($n1 = $f1),*]);
+            let part_two = advent_of_code::solution!(@select selected, part_two, [$($n2 = $f2),*]);
+
+            if solution_args().all_inputs {
+                run_all_inputs(DAY, |input| {
+                    // both parts count as timed out if the parse step does.
+                    let Some(parsed) = try_run_parse($parse, input, DAY) else {
+                        return (None, None);
+                    };
+                    let parsed = &*Box::leak(Box::new(parsed));
+                    (
+                        run_part(part_one, parsed, DAY, advent_of_code::Part::One),
+                        run_part(part_two, parsed, DAY, advent_of_code::Part::Two),
+                    )
+                });
+                return;
+            }
+
+            // parts run on worker threads that may outlive `main` if they time out.
+            let input: &'static str = advent_of_code::template::read_file("inputs", DAY).leak();
+            let parsed = &*Box::leak(Box::new(run_parse($parse, input, DAY)));
+            run_part(part_one, parsed, DAY, advent_of_code::Part::One);
+            run_part(part_two, parsed, DAY, advent_of_code::Part::Two);
+        }
+
+        /// Passes the parse step, the parts and their variants to a benchmark harness.
+        #[allow(dead_code)]
+        pub fn register(registry: &mut impl advent_of_code::template::bench::Registry) {
+            let Some(input) = advent_of_code::template::bench::read_input(DAY) else {
+                registry.skip(DAY, "input file is missing");
+                return;
+            };
+
+            registry.bench(DAY, "parse", input.as_str(), $parse);
+            let parsed = $parse(&input);
+            registry.bench(DAY, "part_one", &parsed, part_one);
+            $(registry.bench(DAY, concat!("part_one/", stringify!($n1)), &parsed, $f1);)*
+            registry.bench(DAY, "part_two", &parsed, part_two);
+            $(registry.bench(DAY, concat!("part_two/", stringify!($n2)), &parsed, $f2);)*
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn test_examples() {
+            advent_of_code::template::examples::assert_examples(
+                DAY,
+                |input: &str| part_one(&$parse(input)),
+                |input: &str| part_two(&$parse(input)),
+            );
+        }
+    };
+    (
+        $day:expr
+        $(; $(part_one: [$($n1:ident = $f1:path),* $(,)?])? $(,)? $(part_two: [$($n2:ident = $f2:path),* $(,)?])?)?
+    ) => {
+        advent_of_code::solution!(
+            @solution $day,
+            [$($($($n1 = $f1),*)?)?],
+            [$($($($n2 = $f2),*)?)?]
+        );
+    };
+    (
+        $day:expr, $parse:path
+        $(; $(part_one: [$($n1:ident = $f1:path),* $(,)?])? $(,)? $(part_two: [$($n2:ident = $f2:path),* $(,)?])?)?
+    ) => {
+        advent_of_code::solution!(
+            @solution $day,
+            $parse,
+            [$($($($n1 = $f1),*)?)?],
+            [$($($($n2 = $f2),*)?)?]
+        );
     };
 }
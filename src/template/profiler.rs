@@ -0,0 +1,171 @@
+/// In-process sampling profiler for `solve DD --profile`.
+///
+/// A step is executed repeatedly for the benchmark budget while [pprof](https://docs.rs/pprof) samples
+/// the stack on a `SIGPROF` timer. The samples are written as folded stacks, which other tools like
+/// [inferno](https://github.com/jonhoo/inferno) understand, and as an SVG flamegraph.
+/// Requires Linux and the `profile` feature, which `solve --profile` enables.
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::{fs, io};
+
+pub const PROFILE_DIR: &str = "target/aoc-profiles";
+
+#[derive(Debug)]
+pub enum Error {
+    /// Built without the `profile` feature or for a platform other than Linux.
+    Unsupported,
+    Profiler(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Unsupported => {
+                write!(f, "profiling requires Linux and the `profile` feature")
+            }
+            Error::Profiler(e) => write!(f, "profiler failed: {e}"),
+            Error::IO(e) => write!(f, "could not write profile: {e}"),
+        }
+    }
+}
+
+/// The files written for a profiled step.
+#[derive(Debug)]
+pub struct Profile {
+    pub folded: PathBuf,
+    pub flamegraph: PathBuf,
+    pub samples: usize,
+}
+
+/// Formats a sampled stack as a line of folded stacks, e.g. `main;part_one;parse 12`.
+/// `frames` is ordered from the innermost frame to the outermost, as it was sampled.
+#[cfg_attr(not(all(feature = "profile", target_os = "linux")), allow(dead_code))]
+fn fold_stack(thread: &str, frames: &[Vec<String>], count: isize) -> String {
+    let names: Vec<&str> = std::iter::once(thread)
+        .chain(
+            frames
+                .iter()
+                .rev()
+                // inlined functions are listed before the function they were inlined into.
+                .flat_map(|x| x.iter().rev().map(String::as_str)),
+        )
+        .collect();
+
+    format!("{} {count}", names.join(";"))
+}
+
+/// Creates the output paths for a step, e.g. `target/aoc-profiles/01-part-1.svg`.
+#[cfg_attr(not(all(feature = "profile", target_os = "linux")), allow(dead_code))]
+fn get_paths(name: &str) -> io::Result<(PathBuf, PathBuf)> {
+    let dir = PathBuf::from(PROFILE_DIR);
+    fs::create_dir_all(&dir)?;
+    Ok((
+        dir.join(format!("{name}.folded")),
+        dir.join(format!("{name}.svg")),
+    ))
+}
+
+#[cfg(all(feature = "profile", target_os = "linux"))]
+mod sampling {
+    use super::{fold_stack, get_paths, Error, Profile};
+    use crate::template::config::config;
+    use std::fs::{self, File};
+    use std::time::Instant;
+
+    /// Samples per second. Not a multiple of common timer frequencies, to avoid lockstep sampling.
+    const FREQUENCY: i32 = 997;
+
+    impl From<pprof::Error> for Error {
+        fn from(e: pprof::Error) -> Self {
+            Error::Profiler(e.to_string())
+        }
+    }
+
+    /// Runs `func` for about the benchmark budget, at least once, and writes its profile.
+    pub fn profile<I: Clone, T>(
+        func: impl Fn(I) -> T,
+        input: &I,
+        name: &str,
+    ) -> Result<Profile, Error> {
+        let budget = config().bench.budget;
+
+        let guard = pprof::ProfilerGuardBuilder::default()
+            .frequency(FREQUENCY)
+            .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+            .build()?;
+
+        let timer = Instant::now();
+        loop {
+            func(input.clone());
+            if timer.elapsed() >= budget {
+                break;
+            }
+        }
+
+        let report = guard.report().build()?;
+        drop(guard);
+
+        let mut lines: Vec<String> = report
+            .data
+            .iter()
+            .map(|(frames, count)| {
+                let names: Vec<Vec<String>> = frames
+                    .frames
+                    .iter()
+                    .map(|x| x.iter().map(ToString::to_string).collect())
+                    .collect();
+                fold_stack(&frames.thread_name_or_id(), &names, *count)
+            })
+            .collect();
+        lines.sort_unstable();
+
+        let (folded, flamegraph) = get_paths(name)?;
+        fs::write(&folded, lines.join("\n") + "\n")?;
+        report.flamegraph(File::create(&flamegraph)?)?;
+
+        Ok(Profile {
+            folded,
+            flamegraph,
+            samples: report.data.values().map(|x| x.unsigned_abs()).sum(),
+        })
+    }
+}
+
+#[cfg(all(feature = "profile", target_os = "linux"))]
+pub use sampling::profile;
+
+/// Stub for builds without profiler support.
+#[cfg(not(all(feature = "profile", target_os = "linux")))]
+pub fn profile<I: Clone, T>(
+    _func: impl Fn(I) -> T,
+    _input: &I,
+    _name: &str,
+) -> Result<Profile, Error> {
+    Err(Error::Unsupported)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::fold_stack;
+
+    #[test]
+    fn folds_stack() {
+        let frames = vec![
+            vec!["parse".to_string(), "part_one".to_string()],
+            vec!["run_part".to_string()],
+            vec!["main".to_string()],
+        ];
+
+        assert_eq!(
+            fold_stack("main", &frames, 12),
+            "main;main;run_part;part_one;parse 12"
+        );
+    }
+}
@@ -0,0 +1,215 @@
+/// Helpers that extract information from puzzle descriptions saved by `download` / `read`.
+/// Descriptions are expected to be Markdown, but code blocks and emphasis may also use raw HTML.
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::template::config::config;
+use crate::{Day, Part};
+
+const PART_TWO_MARKER: &str = "--- Part Two ---";
+
+/// A code block found in a puzzle description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The part in whose description the block appears.
+    pub part: Part,
+    pub content: String,
+}
+
+#[must_use]
+pub fn get_puzzle_path(day: Day) -> PathBuf {
+    config().puzzles_dir().join(format!("{day}.md"))
+}
+
+/// Splits a description into the text of part one and, if unlocked, part two.
+#[must_use]
+pub fn split_parts(description: &str) -> (&str, Option<&str>) {
+    match description.find(PART_TWO_MARKER) {
+        Some(pos) => {
+            // keep the heading line of part two out of part one.
+            let start = description[..pos].rfind('\n').map_or(0, |x| x + 1);
+            (&description[..start], Some(&description[start..]))
+        }
+        None => (description, None),
+    }
+}
+
+/// Matches emphasized code spans in Markdown or HTML, the content is in one of the groups `a` to `d`.
+pub(crate) const EMPHASIZED_CODE_PATTERN: &str = concat!(
+    r"<code><em>(?P<a>[^<]+)</em></code>",
+    r"|<em><code>(?P<b>[^<]+)</code></em>",
+    r"|`\*{1,2}(?P<c>[^`*]+)\*{1,2}`",
+    r"|\*{1,2}`(?P<d>[^`]+)`\*{1,2}",
+);
+
+pub(crate) fn unescape_html(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+pub(crate) fn strip_tags(s: &str) -> String {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    let tag = TAG.get_or_init(|| Regex::new(r"</?[a-z]+>").unwrap());
+    tag.replace_all(s, "").to_string()
+}
+
+fn extract_code_blocks_from(text: &str, part: Part) -> Vec<CodeBlock> {
+    static BLOCK: OnceLock<Regex> = OnceLock::new();
+    let block = BLOCK.get_or_init(|| {
+        Regex::new(r"(?s)<pre><code>(?P<html>.*?)</code></pre>|```[^\n]*\n(?P<md>.*?)```").unwrap()
+    });
+
+    block
+        .captures_iter(text)
+        .filter_map(|captures| {
+            let content = match (captures.name("html"), captures.name("md")) {
+                (Some(html), _) => unescape_html(&strip_tags(html.as_str())),
+                (None, Some(md)) => md.as_str().to_string(),
+                (None, None) => return None,
+            };
+
+            let content = content.trim_end_matches('\n');
+            if content.trim().is_empty() {
+                return None;
+            }
+
+            Some(CodeBlock {
+                part,
+                content: format!("{content}\n"),
+            })
+        })
+        .collect()
+}
+
+/// Returns all code blocks of a description in order of appearance.
+#[must_use]
+pub fn extract_code_blocks(description: &str) -> Vec<CodeBlock> {
+    let (part_one, part_two) = split_parts(description);
+    let mut blocks = extract_code_blocks_from(part_one, Part::One);
+
+    if let Some(part_two) = part_two {
+        blocks.append(&mut extract_code_blocks_from(part_two, Part::Two));
+    }
+
+    blocks
+}
+
+/// Returns the last emphasized code span of a text, e.g. `<code><em>142</em></code>` or `` `*142*` ``.
+/// In puzzle descriptions, this is usually the answer for the example.
+fn extract_last_emphasized_code(text: &str) -> Option<String> {
+    static SPAN: OnceLock<Regex> = OnceLock::new();
+    let span = SPAN.get_or_init(|| Regex::new(EMPHASIZED_CODE_PATTERN).unwrap());
+
+    span.captures_iter(text).last().and_then(|captures| {
+        ["a", "b", "c", "d"]
+            .iter()
+            .find_map(|name| captures.name(name))
+            .map(|x| unescape_html(x.as_str().trim()))
+    })
+}
+
+/// Proposes expected answers for part one and two from a description.
+#[must_use]
+pub fn extract_answers(description: &str) -> (Option<String>, Option<String>) {
+    let (part_one, part_two) = split_parts(description);
+    (
+        extract_last_emphasized_code(part_one),
+        part_two.and_then(extract_last_emphasized_code),
+    )
+}
+
+/// Returns the title of a puzzle from its heading, e.g. `Trebuchet?!` for `--- Day 1: Trebuchet?! ---`.
+#[must_use]
+pub fn extract_title(description: &str) -> Option<String> {
+    static HEADING: OnceLock<Regex> = OnceLock::new();
+    let heading = HEADING.get_or_init(|| Regex::new(r"--- Day \d+: (.+?) ---").unwrap());
+
+    heading
+        .captures(description)
+        .map(|captures| unescape_html(captures[1].trim()))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract_answers, extract_code_blocks, extract_title, CodeBlock};
+    use crate::Part;
+
+    const MARKDOWN: &str = r#"## --- Day 1: Trebuchet?! ---
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values are `12` and `38`. Adding these together produces `*50*`.
+
+## --- Part Two ---
+
+For example:
+
+```
+two1nine
+eightwothree
+```
+
+Adding these together produces `*281*`.
+"#;
+
+    const HTML: &str = r#"<h2>--- Day 1: Trebuchet?! ---</h2>
+<pre><code>a &lt; b
+<em>c</em>
+</code></pre>
+<p>Produces <code><em>142</em></code>.</p>
+"#;
+
+    #[test]
+    fn extracts_markdown_code_blocks() {
+        assert_eq!(
+            extract_code_blocks(MARKDOWN),
+            vec![
+                CodeBlock {
+                    part: Part::One,
+                    content: "1abc2\npqr3stu8vwx\n".into()
+                },
+                CodeBlock {
+                    part: Part::Two,
+                    content: "two1nine\neightwothree\n".into()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_html_code_blocks() {
+        assert_eq!(
+            extract_code_blocks(HTML),
+            vec![CodeBlock {
+                part: Part::One,
+                content: "a < b\nc\n".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn extracts_answers() {
+        assert_eq!(
+            extract_answers(MARKDOWN),
+            (Some("50".into()), Some("281".into()))
+        );
+        assert_eq!(extract_answers(HTML), (Some("142".into()), None));
+    }
+
+    #[test]
+    fn extracts_title() {
+        assert_eq!(extract_title(MARKDOWN), Some("Trebuchet?!".into()));
+        assert_eq!(extract_title(HTML), Some("Trebuchet?!".into()));
+        assert_eq!(extract_title("no heading"), None);
+    }
+}
@@ -0,0 +1,97 @@
+/// Generated sections of the readme. A section is delimited by two occurrences of a marker comment,
+/// e.g. `<!--- benchmarking table --->`, and replaced as a whole when it is updated.
+use std::fmt::Display;
+use std::ops::Range;
+use std::{fs, io};
+
+use crate::template::config::config;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "could not access readme: {e}"),
+        }
+    }
+}
+
+/// Returns the range of a section, including its markers.
+fn locate_section(readme: &str, marker: &str) -> Result<Range<usize>, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
+
+    if matches.len() > 2 {
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
+    }
+
+    let pos_start = matches.first().map(|m| m.0).ok_or_else(|| {
+        Error::Parser(format!("{marker}: could not find section start position."))
+    })?;
+
+    let pos_end = matches
+        .last()
+        .map(|m| m.0 + m.1.len())
+        .ok_or_else(|| Error::Parser(format!("{marker}: could not find section end position.")))?;
+
+    Ok(pos_start..pos_end)
+}
+
+/// Replaces the content between the markers of a section.
+pub fn replace_section(readme: &mut String, marker: &str, content: &str) -> Result<(), Error> {
+    let range = locate_section(readme, marker)?;
+    readme.replace_range(range, &format!("{marker}\n{content}\n{marker}"));
+    Ok(())
+}
+
+/// Replaces a section of the readme file of the project.
+pub fn update_section(marker: &str, content: &str) -> Result<(), Error> {
+    let path = &config().readme;
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    replace_section(&mut readme, marker, content)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::replace_section;
+
+    const MARKER: &str = "<!--- table --->";
+
+    #[test]
+    fn replaces_section() {
+        let mut s = format!("foo\n{MARKER}\nold\n{MARKER}\nbar");
+        replace_section(&mut s, MARKER, "new").unwrap();
+        assert_eq!(s, format!("foo\n{MARKER}\nnew\n{MARKER}\nbar"));
+    }
+
+    #[test]
+    fn replaces_only_its_section() {
+        let other = "<!--- other --->";
+        let mut s = format!("{other}\nkeep\n{other}\n{MARKER}{MARKER}");
+        replace_section(&mut s, MARKER, "new").unwrap();
+        assert_eq!(
+            s,
+            format!("{other}\nkeep\n{other}\n{MARKER}\nnew\n{MARKER}")
+        );
+    }
+
+    #[test]
+    fn errors_on_missing_or_extra_markers() {
+        assert!(replace_section(&mut "# readme".to_string(), MARKER, "").is_err());
+        assert!(replace_section(&mut format!("{MARKER}{MARKER}{MARKER}"), MARKER, "").is_err());
+    }
+}
@@ -1,131 +1,620 @@
 /// Module that updates the readme me with timing information.
-/// The approach taken is similar to how `aoc-readme-stars` handles this.
+/// The approach taken is similar to how `aoc-readme-stars` handles this, see [`readme`].
+/// The layout of the table is set in the `[table]` section of the project config, see [`TableConfig`].
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
 use std::{fs, io};
 
-use crate::Day;
+use crate::template::allocator::AllocStats;
+use crate::template::config::{config, TableConfig};
+use crate::template::readme;
+use crate::{Day, Part};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
+/// Day totals of the previous run in nanoseconds, used for the speed-up column.
+const HISTORY_PATH: &str = "target/aoc-benchmarks.toml";
+
+/// Width of the bar charts in the share column, in characters.
+const BAR_WIDTH: usize = 10;
+
+pub use crate::template::readme::Error;
+
+/// Timings of the parse step and both parts of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timings {
+    pub day: Day,
+    pub parse: Option<Timing>,
+    pub part_1: Option<Timing>,
+    pub part_2: Option<Timing>,
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+impl Timings {
+    /// Sum of the mean times of all steps. Timed out steps do not count.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        [&self.parse, &self.part_1, &self.part_2]
+            .into_iter()
+            .flatten()
+            .filter_map(Timing::measurement)
+            .map(|x| x.mean)
+            .sum()
     }
 }
 
-#[derive(Clone)]
-pub struct Timings {
-    pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+/// Timing of a single step, as reported by the solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Timing {
+    Measured(Measurement),
+    /// The step did not finish within the time budget.
+    TimedOut(Duration),
+}
+
+impl Timing {
+    #[must_use]
+    pub fn measurement(&self) -> Option<&Measurement> {
+        match self {
+            Timing::Measured(measurement) => Some(measurement),
+            Timing::TimedOut(_) => None,
+        }
+    }
+}
+
+impl Display for Timing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Timing::Measured(measurement) => write!(f, "{:.1?}", measurement.mean),
+            Timing::TimedOut(timeout) => write!(f, "TIMEOUT after {timeout:?}"),
+        }
+    }
+}
+
+/// Benchmark statistics of a step. Median and standard deviation are missing in the
+/// output of older solution bins.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Measurement {
+    pub mean: Duration,
+    pub median: Option<Duration>,
+    pub stddev: Option<Duration>,
+    pub samples: u128,
+    /// Allocations of one run, if the solution was run with the counting allocator.
+    pub allocs: Option<AllocStats>,
+}
+
+/// Starts the machine-readable line that solutions print after the timing of a step, see [`format_record`].
+pub const RECORD_PREFIX: &str = "@timing ";
+
+/// Formats a measurement with exact numbers, e.g.
+/// `@timing Part 1: mean_ns=1249000 median_ns=1248000 stddev_ns=2100 samples=10 allocs=3 alloc_bytes=1536 peak_bytes=1024`.
+/// The allocation fields are only present if allocations were counted.
+#[must_use]
+pub fn format_record(step: &str, measurement: &Measurement) -> String {
+    let nanos = |x: Option<Duration>| x.map_or_else(|| "-".into(), |x| x.as_nanos().to_string());
+
+    let mut record = format!(
+        "{RECORD_PREFIX}{step}: mean_ns={} median_ns={} stddev_ns={} samples={}",
+        measurement.mean.as_nanos(),
+        nanos(measurement.median),
+        nanos(measurement.stddev),
+        measurement.samples
+    );
+
+    if let Some(allocs) = &measurement.allocs {
+        record += &format!(
+            " allocs={} alloc_bytes={} peak_bytes={}",
+            allocs.allocations, allocs.bytes, allocs.peak_bytes
+        );
+    }
+
+    record
+}
+
+/// Parses a line written by [`format_record`] into the step name and its measurement.
+#[must_use]
+pub fn parse_record(line: &str) -> Option<(&str, Measurement)> {
+    let (step, fields) = line.strip_prefix(RECORD_PREFIX)?.split_once(": ")?;
+
+    let fields: BTreeMap<&str, &str> = fields
+        .split_whitespace()
+        .filter_map(|x| x.split_once('='))
+        .collect();
+
+    let number = |key: &str| fields.get(key)?.parse::<u64>().ok();
+    let duration = |key: &str| number(key).map(Duration::from_nanos);
+
+    let allocs = match (
+        number("allocs"),
+        number("alloc_bytes"),
+        number("peak_bytes"),
+    ) {
+        (Some(allocations), Some(bytes), Some(peak_bytes)) => Some(AllocStats {
+            allocations,
+            bytes,
+            peak_bytes,
+        }),
+        _ => None,
+    };
+
+    Some((
+        step,
+        Measurement {
+            mean: duration("mean_ns")?,
+            median: duration("median_ns"),
+            stddev: duration("stddev_ns"),
+            samples: fields.get("samples")?.parse().ok()?,
+            allocs,
+        },
+    ))
+}
+
+/// A column group of the benchmark table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// Mean time of the parse step and both parts.
+    Time,
+    Median,
+    Stddev,
+    Samples,
+    /// Allocations of both parts, only shown if the solutions were run with `--allocs`.
+    Allocs,
+    /// Speed-up of the day compared to the previous run.
+    Speedup,
+    /// Share of the day in the total time, as a bar chart.
+    Share,
+}
+
+const COLUMNS: [(Column, &str); 7] = [
+    (Column::Time, "time"),
+    (Column::Median, "median"),
+    (Column::Stddev, "stddev"),
+    (Column::Samples, "samples"),
+    (Column::Allocs, "allocs"),
+    (Column::Speedup, "speedup"),
+    (Column::Share, "share"),
+];
+
+impl Display for Column {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = COLUMNS.iter().find(|x| x.0 == *self).map_or("", |x| x.1);
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        COLUMNS
+            .iter()
+            .find(|x| x.1 == s)
+            .map(|x| x.0)
+            .ok_or_else(|| {
+                let names: Vec<&str> = COLUMNS.iter().map(|x| x.1).collect();
+                format!("unknown column `{s}`, expected one of {}", names.join(", "))
+            })
+    }
+}
+
+/// Order of the days in the benchmark table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Sort {
+    #[default]
+    Day,
+    Slowest,
+    Fastest,
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+impl Display for Sort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Sort::Day => f.write_str("day"),
+            Sort::Slowest => f.write_str("slowest"),
+            Sort::Fastest => f.write_str("fastest"),
+        }
+    }
+}
+
+impl FromStr for Sort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Sort::Day),
+            "slowest" => Ok(Sort::Slowest),
+            "fastest" => Ok(Sort::Fastest),
+            _ => Err(format!(
+                "unknown sort order `{s}`, expected one of day, slowest, fastest"
+            )),
+        }
+    }
 }
 
+/// Day totals of a run.
+type History = BTreeMap<Day, Duration>;
+
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+enum Cell {
+    Link { text: String, href: String },
+    Code { text: String, strong: bool },
+}
 
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+impl Cell {
+    fn code(text: impl Into<String>) -> Self {
+        Cell::Code {
+            text: text.into(),
+            strong: false,
+        }
+    }
+
+    fn optional(text: Option<impl Into<String>>) -> Self {
+        text.map_or_else(|| Cell::code("-"), Cell::code)
     }
+}
+
+/// Format-independent benchmark table, rendered as markdown or HTML.
+struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+    total: Duration,
+}
+
+/// Renders a share between 0 and 1 as a bar of [`BAR_WIDTH`] characters, with eighths of a character.
+fn format_bar(share: f64) -> String {
+    const EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let eighths = (share.clamp(0.0, 1.0) * (BAR_WIDTH * 8) as f64).round() as usize;
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+    let (full, rest) = (eighths / 8, eighths % 8);
 
-    Ok(TablePosition { pos_start, pos_end })
+    let mut bar = "█".repeat(full);
+    if rest > 0 {
+        bar.push(EIGHTHS[rest]);
+    }
+
+    let padding = BAR_WIDTH - bar.chars().count();
+    bar + &" ".repeat(padding)
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Returns the days and parts of the `count` slowest parts.
+fn slowest_parts(timings: &[Timings], count: usize) -> Vec<(Day, Part)> {
+    let mut parts: Vec<(Day, Part, Duration)> = timings
+        .iter()
+        .flat_map(|x| {
+            [(Part::One, &x.part_1), (Part::Two, &x.part_2)]
+                .into_iter()
+                .filter_map(|(part, timing)| {
+                    Some((x.day, part, timing.as_ref()?.measurement()?.mean))
+                })
+        })
+        .collect();
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
+    parts.sort_by_key(|x| std::cmp::Reverse(x.2));
+    parts.into_iter().take(count).map(|x| (x.0, x.1)).collect()
+}
+
+fn allocs(timing: Option<&Timing>) -> Option<String> {
+    timing?.measurement()?.allocs.map(|x| x.to_string())
+}
+
+fn build_table(mut timings: Vec<Timings>, options: &TableConfig, previous: &History) -> Table {
+    let has = |column: Column| options.columns.contains(&column);
+    let total: Duration = timings.iter().map(Timings::total).sum();
+
+    // allocation columns are only shown if the solutions were run with the counting allocator.
+    let has_allocs = has(Column::Allocs)
+        && timings
+            .iter()
+            .any(|x| allocs(x.part_1.as_ref()).is_some() || allocs(x.part_2.as_ref()).is_some());
+
+    match options.sort {
+        Sort::Day => timings.sort_by_key(|x| x.day),
+        Sort::Slowest => timings.sort_by_key(|x| std::cmp::Reverse(x.total())),
+        Sort::Fastest => timings.sort_by_key(Timings::total),
+    }
+
+    let slowest = slowest_parts(&timings, options.highlight);
+
+    let mut header: Vec<String> = vec!["Day".into()];
+
+    for column in &options.columns {
+        match column {
+            Column::Time => header.extend(["Parse".into(), "Part 1".into(), "Part 2".into()]),
+            Column::Median => header.extend(["Part 1 median".into(), "Part 2 median".into()]),
+            Column::Stddev => header.extend(["Part 1 σ".into(), "Part 2 σ".into()]),
+            Column::Samples => header.extend(["Part 1 samples".into(), "Part 2 samples".into()]),
+            Column::Allocs if has_allocs => {
+                header.extend(["Part 1 memory".into(), "Part 2 memory".into()]);
+            }
+            Column::Allocs => {}
+            Column::Speedup => header.push("Speed-up".into()),
+            Column::Share => header.push("Share".into()),
+        }
+    }
+
+    let format = |x: Duration| format!("{x:.1?}");
+
+    let rows = timings
+        .into_iter()
+        .map(|timing| {
+            let parts = [
+                (Part::One, timing.part_1.as_ref()),
+                (Part::Two, timing.part_2.as_ref()),
+            ];
+            let measurements = parts.map(|x| x.1.and_then(Timing::measurement));
+            let day_total = timing.total();
+
+            let mut row = vec![Cell::Link {
+                text: format!("Day {}", timing.day.into_inner()),
+                href: get_path_for_bin(timing.day),
+            }];
+
+            for column in &options.columns {
+                match column {
+                    Column::Time => {
+                        row.push(Cell::optional(
+                            timing.parse.as_ref().map(ToString::to_string),
+                        ));
+                        row.extend(parts.iter().map(|(part, time)| Cell::Code {
+                            text: time.map_or_else(|| "-".into(), ToString::to_string),
+                            strong: slowest.contains(&(timing.day, *part)),
+                        }));
+                    }
+                    Column::Median => row.extend(
+                        measurements.map(|x| Cell::optional(x.and_then(|x| x.median).map(format))),
+                    ),
+                    Column::Stddev => row.extend(
+                        measurements.map(|x| Cell::optional(x.and_then(|x| x.stddev).map(format))),
+                    ),
+                    Column::Samples => row.extend(
+                        measurements.map(|x| Cell::optional(x.map(|x| x.samples.to_string()))),
+                    ),
+                    Column::Allocs if has_allocs => {
+                        row.extend(parts.map(|x| Cell::optional(allocs(x.1))));
+                    }
+                    Column::Allocs => {}
+                    Column::Speedup => {
+                        let speedup = previous
+                            .get(&timing.day)
+                            .filter(|_| !day_total.is_zero())
+                            .map(|x| format!("{:.2}×", x.as_secs_f64() / day_total.as_secs_f64()));
+                        row.push(Cell::optional(speedup));
+                    }
+                    Column::Share => {
+                        let share = day_total.as_secs_f64() / total.as_secs_f64();
+                        let share = if share.is_finite() { share } else { 0.0 };
+                        row.push(Cell::code(format!(
+                            "{} {:.1}%",
+                            format_bar(share),
+                            share * 100.0
+                        )));
+                    }
+                }
+            }
+
+            row
+        })
+        .collect();
+
+    Table {
         header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+        rows,
+        total,
+    }
+}
 
-    for timing in timings {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+fn render_markdown(table: &Table, prefix: &str) -> String {
+    let mut lines: Vec<String> = vec![format!("{prefix} Benchmarks"), String::new()];
+
+    lines.push(format!("| {} |", table.header.join(" | ")));
+    lines.push(format!(
+        "| {}  |",
+        vec![":---:"; table.header.len()].join(" | ")
+    ));
+
+    for row in &table.rows {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|cell| match cell {
+                Cell::Link { text, href } => format!("[{text}]({href})"),
+                Cell::Code {
+                    text,
+                    strong: false,
+                } => format!("`{text}`"),
+                Cell::Code { text, strong: true } => format!("**`{text}`**"),
+            })
+            .collect();
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(format!(
+        "**Total: {:.2}ms**",
+        table.total.as_secs_f64() * 1000.0
+    ));
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
-    Ok(())
+fn render_html(table: &Table) -> String {
+    let mut lines: Vec<String> = vec![
+        "<h2>Benchmarks</h2>".into(),
+        "<table>".into(),
+        "<thead>".into(),
+    ];
+
+    let header: Vec<String> = table
+        .header
+        .iter()
+        .map(|x| format!("<th>{}</th>", escape_html(x)))
+        .collect();
+    lines.push(format!("<tr>{}</tr>", header.concat()));
+    lines.push("</thead>".into());
+    lines.push("<tbody>".into());
+
+    for row in &table.rows {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|cell| match cell {
+                Cell::Link { text, href } => {
+                    format!("<td><a href=\"{href}\">{}</a></td>", escape_html(text))
+                }
+                Cell::Code {
+                    text,
+                    strong: false,
+                } => {
+                    format!("<td><code>{}</code></td>", escape_html(text))
+                }
+                Cell::Code { text, strong: true } => {
+                    format!(
+                        "<td><strong><code>{}</code></strong></td>",
+                        escape_html(text)
+                    )
+                }
+            })
+            .collect();
+
+        lines.push(format!("<tr>{}</tr>", cells.concat()));
+    }
+
+    lines.push("</tbody>".into());
+    lines.push("</table>".into());
+    lines.push(format!(
+        "<p><strong>Total: {:.2}ms</strong></p>",
+        table.total.as_secs_f64() * 1000.0
+    ));
+
+    lines.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    timings: Vec<Timings>,
+    options: &TableConfig,
+    previous: &History,
+) -> Result<(), Error> {
+    let table = build_table(timings, options, previous);
+    readme::replace_section(s, MARKER, &render_markdown(&table, "##"))
+}
+
+fn read_history() -> History {
+    let Ok(contents) = fs::read_to_string(HISTORY_PATH) else {
+        return History::new();
+    };
+
+    let Ok(table) = contents.parse::<toml::Table>() else {
+        return History::new();
+    };
+
+    table
+        .iter()
+        .filter_map(|(day, nanos)| {
+            let nanos = u64::try_from(nanos.as_integer()?).ok()?;
+            Some((day.parse().ok()?, Duration::from_nanos(nanos)))
+        })
+        .collect()
+}
+
+fn write_history(timings: &[Timings]) -> io::Result<()> {
+    let table: toml::Table = timings
+        .iter()
+        .map(|x| {
+            let nanos = i64::try_from(x.total().as_nanos()).unwrap_or(i64::MAX);
+            (x.day.to_string(), toml::Value::Integer(nanos))
+        })
+        .collect();
+
+    if let Some(parent) = Path::new(HISTORY_PATH).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(HISTORY_PATH, table.to_string())
+}
+
+/// Writes the benchmark table to the readme, or to the file set as `table.output`.
+/// Files ending in `.html` get an HTML table, all others a markdown table.
+/// Returns the path of the updated file.
+pub fn update(timings: Vec<Timings>) -> Result<&'static Path, Error> {
+    let options = &config().table;
+    let previous = read_history();
+    // the history is only replaced once the table was written, a failed update keeps the speed-ups.
+    let history = timings.clone();
+
+    let path = if let Some(path) = &options.output {
+        let table = build_table(timings, options, &previous);
+        let contents = if path.extension().is_some_and(|x| x == "html") {
+            render_html(&table)
+        } else {
+            render_markdown(&table, "#")
+        };
+        fs::write(path, contents + "\n")?;
+        path
+    } else {
+        let path = &config().readme;
+        let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+        update_content(&mut readme, timings, options, &previous)?;
+        fs::write(path, &readme)?;
+        path
+    };
+
+    write_history(&history)?;
+    Ok(path)
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{
+        build_table, format_bar, format_record, parse_record, render_html, render_markdown,
+        update_content, Column, History, Measurement, Sort, Timing, Timings, MARKER,
+    };
     use crate::day;
+    use crate::template::allocator::AllocStats;
+    use crate::template::config::TableConfig;
+    use std::time::Duration;
+
+    fn timing(millis: u64) -> Option<Timing> {
+        Some(Timing::Measured(Measurement {
+            mean: Duration::from_millis(millis),
+            median: Some(Duration::from_millis(1)),
+            stddev: Some(Duration::from_micros(2)),
+            samples: 10,
+            allocs: None,
+        }))
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
-                total_nanos: 3e+10,
+                parse: None,
+                part_1: timing(10),
+                part_2: timing(20),
             },
             Timings {
                 day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
-                total_nanos: 7e+10,
+                parse: timing(5),
+                part_1: timing(30),
+                part_2: timing(40),
             },
             Timings {
                 day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
-                total_nanos: 9e+10,
+                parse: None,
+                part_1: timing(40),
+                part_2: timing(50),
             },
         ]
     }
@@ -134,28 +623,58 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            &TableConfig::default(),
+            &History::new(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            &TableConfig::default(),
+            &History::new(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            &TableConfig::default(),
+            &History::new(),
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            &TableConfig::default(),
+            &History::new(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            &TableConfig::default(),
+            &History::new(),
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,24 +682,171 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            &TableConfig::default(),
+            &History::new(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40.0ms` | `50.0ms` |",
             "",
-            "**Total: 190.00ms**",
+            "**Total: 195.00ms**",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn round_trips_records() {
+        let mut measurement = Measurement {
+            mean: Duration::from_nanos(1_249_013),
+            median: Some(Duration::from_nanos(1_248_001)),
+            stddev: Some(Duration::from_nanos(2_107)),
+            samples: 10,
+            allocs: None,
+        };
+
+        let record = format_record("Part 1", &measurement);
+        assert_eq!(
+            record,
+            "@timing Part 1: mean_ns=1249013 median_ns=1248001 stddev_ns=2107 samples=10"
+        );
+        assert_eq!(parse_record(&record), Some(("Part 1", measurement.clone())));
+
+        measurement.median = None;
+        measurement.allocs = Some(AllocStats {
+            allocations: 3,
+            bytes: 1536,
+            peak_bytes: 1024,
+        });
+        let record = format_record("Parse", &measurement);
+        assert!(record.ends_with("allocs=3 alloc_bytes=1536 peak_bytes=1024"));
+        assert_eq!(parse_record(&record), Some(("Parse", measurement)));
+
+        assert_eq!(parse_record("Part 1: 42 (1.2ms @ 10 samples)"), None);
+        assert_eq!(parse_record("@timing Part 1: samples=10"), None);
+    }
+
+    #[test]
+    fn format_benchmarks_with_allocs() {
+        let mut timings = get_mock_timings();
+        if let Some(Timing::Measured(measurement)) = &mut timings[0].part_1 {
+            measurement.allocs = Some(AllocStats {
+                allocations: 3,
+                bytes: 1536,
+                peak_bytes: 1024,
+            });
+        }
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, &TableConfig::default(), &History::new()).unwrap();
+
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 | Part 1 memory | Part 2 memory |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `20.0ms` | `3 allocs, 1.5KiB total, 1.0KiB peak` | `-` |"
+        ));
+        assert!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `5.0ms` | `30.0ms` | `40.0ms` | `-` | `-` |")
+        );
+    }
+
+    #[test]
+    fn sorts_and_highlights_slowest_parts() {
+        let options = TableConfig {
+            sort: Sort::Slowest,
+            highlight: 2,
+            ..TableConfig::default()
+        };
+
+        let table = build_table(get_mock_timings(), &options, &History::new());
+        let s = render_markdown(&table, "##");
+        let rows: Vec<&str> = s.lines().filter(|x| x.starts_with("| [Day")).collect();
+
+        assert_eq!(
+            rows,
+            [
+                "| [Day 4](./src/bin/04.rs) | `-` | **`40.0ms`** | **`50.0ms`** |",
+                "| [Day 2](./src/bin/02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |",
+                "| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            ]
+        );
+    }
+
+    #[test]
+    fn format_extra_columns() {
+        let options = TableConfig {
+            columns: vec![
+                Column::Median,
+                Column::Stddev,
+                Column::Samples,
+                Column::Speedup,
+                Column::Share,
+            ],
+            ..TableConfig::default()
+        };
+
+        let previous = History::from([(day!(1), Duration::from_millis(60))]);
+        let table = build_table(get_mock_timings(), &options, &previous);
+        let s = render_markdown(&table, "##");
+
+        assert!(s.contains(
+            "| Day | Part 1 median | Part 2 median | Part 1 σ | Part 2 σ | Part 1 samples | Part 2 samples | Speed-up | Share |"
+        ));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `1.0ms` | `1.0ms` | `2.0µs` | `2.0µs` | `10` | `10` | `2.00×` | `█▌         15.4%` |"
+        ));
+        assert!(s.contains("| `-` | `████▋      46.2%` |"));
+    }
+
+    #[test]
+    fn format_bars() {
+        assert_eq!(format_bar(0.0), "          ");
+        assert_eq!(format_bar(0.0125), "▏         ");
+        assert_eq!(format_bar(0.5), "█████     ");
+        assert_eq!(format_bar(1.5), "██████████");
+    }
+
+    #[test]
+    fn format_html() {
+        let options = TableConfig {
+            highlight: 1,
+            ..TableConfig::default()
+        };
+
+        let table = build_table(get_mock_timings(), &options, &History::new());
+        let s = render_html(&table);
+
+        assert!(
+            s.starts_with("<h2>Benchmarks</h2>\n<table>\n<thead>\n<tr><th>Day</th><th>Parse</th>")
+        );
+        assert!(s.contains(
+            "<tr><td><a href=\"./src/bin/04.rs\">Day 4</a></td><td><code>-</code></td><td><code>40.0ms</code></td><td><strong><code>50.0ms</code></strong></td></tr>"
+        ));
+        assert!(s.ends_with("</table>\n<p><strong>Total: 195.00ms</strong></p>"));
+    }
+
+    #[test]
+    fn format_timeouts() {
+        let mut timings = get_mock_timings();
+        timings[0].part_2 = Some(Timing::TimedOut(Duration::from_secs(5)));
+
+        let table = build_table(timings, &TableConfig::default(), &History::new());
+        let s = render_markdown(&table, "##");
+
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `TIMEOUT after 5s` |"));
+        assert!(s.contains("**Total: 175.00ms**"));
+    }
 }
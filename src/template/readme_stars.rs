@@ -0,0 +1,234 @@
+/// Module that updates the readme with the stars collected per day, like the `advent-readme-stars` action.
+/// Stars are taken from the answers recorded by `solve --submit`, or from a private leaderboard JSON file.
+use std::fs;
+
+use serde_json::Value;
+
+use crate::template::readme::{self, Error};
+use crate::template::{answers::Answers, puzzle};
+use crate::{Day, Part, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stars {
+    pub day: Day,
+    pub part_one: bool,
+    pub part_two: bool,
+    pub title: Option<String>,
+}
+
+impl Stars {
+    fn new(day: Day) -> Self {
+        Self {
+            day,
+            part_one: false,
+            part_two: false,
+            title: None,
+        }
+    }
+
+    fn collect(&mut self, part: Part) {
+        match part {
+            Part::One => self.part_one = true,
+            Part::Two => self.part_two = true,
+        }
+    }
+}
+
+/// Collects the stars of a year from the recorded answers.
+#[must_use]
+pub fn stars_from_answers(answers: &Answers, year: Year) -> Vec<Stars> {
+    let mut stars: Vec<Stars> = vec![];
+
+    for key in answers.keys().filter(|x| x.year == year) {
+        if stars.last().map(|x| x.day) != Some(key.day) {
+            stars.push(Stars::new(key.day));
+        }
+
+        if let Some(day) = stars.last_mut() {
+            day.collect(key.part);
+        }
+    }
+
+    stars
+}
+
+/// Collects the stars of a member from a private leaderboard JSON file, as served by
+/// `https://adventofcode.com/YYYY/leaderboard/private/view/ID.json`.
+/// Without a member id, the stars of the owner of the leaderboard are used.
+/// Returns the year of the leaderboard and the stars.
+pub fn stars_from_leaderboard(
+    json: &str,
+    member: Option<&str>,
+) -> Result<(Option<Year>, Vec<Stars>), Error> {
+    let leaderboard: Value = serde_json::from_str(json)
+        .map_err(|e| Error::Parser(format!("invalid leaderboard: {e}")))?;
+
+    let year = leaderboard["event"].as_str().and_then(|x| x.parse().ok());
+
+    let member_id = match member {
+        Some(member) => member.to_string(),
+        None => leaderboard["owner_id"].to_string(),
+    };
+
+    let levels = leaderboard["members"][&member_id]["completion_day_level"]
+        .as_object()
+        .ok_or_else(|| Error::Parser(format!("member `{member_id}` not found in leaderboard")))?;
+
+    let mut stars: Vec<Stars> = levels
+        .iter()
+        .filter_map(|(day, parts)| {
+            let mut stars = Stars::new(day.parse().ok()?);
+            for part in parts.as_object()?.keys().filter_map(|x| x.parse().ok()) {
+                stars.collect(part);
+            }
+            Some(stars)
+        })
+        .collect();
+
+    stars.sort_by_key(|x| x.day);
+    Ok((year, stars))
+}
+
+/// Adds the titles of the puzzle descriptions saved by `download` to the stars.
+pub fn add_titles(stars: &mut [Stars]) {
+    for day in stars {
+        day.title = fs::read_to_string(puzzle::get_puzzle_path(day.day))
+            .ok()
+            .and_then(|x| puzzle::extract_title(&x));
+    }
+}
+
+fn construct_table(prefix: &str, year: Year, stars: &[Stars]) -> String {
+    let star = |collected: bool| if collected { "⭐" } else { " " };
+
+    let mut lines: Vec<String> = vec![
+        format!("{prefix} {year} Results"),
+        String::new(),
+        "| Day | Title | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for day in stars {
+        lines.push(format!(
+            "| [Day {}](https://adventofcode.com/{year}/day/{}) | {} | {} | {} |",
+            day.day.into_inner(),
+            day.day.into_inner(),
+            day.title.as_deref().unwrap_or_default().replace('|', "\\|"),
+            star(day.part_one),
+            star(day.part_two)
+        ));
+    }
+
+    lines.join("\n")
+}
+
+pub fn update(year: Year, stars: &[Stars]) -> Result<(), Error> {
+    let table = construct_table("##", year, stars);
+    readme::update_section(MARKER, &table)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{construct_table, stars_from_answers, stars_from_leaderboard, Stars, MARKER};
+    use crate::template::{answers::Answers, readme};
+    use crate::{day, Part, PuzzleKey, Year};
+
+    const LEADERBOARD: &str = r#"{
+        "event": "2023",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1,
+                "completion_day_level": {
+                    "10": { "1": { "get_star_ts": 1702200000, "star_index": 3 } },
+                    "2": {
+                        "1": { "get_star_ts": 1701500000, "star_index": 1 },
+                        "2": { "get_star_ts": 1701500100, "star_index": 2 }
+                    }
+                }
+            },
+            "2": { "id": 2, "completion_day_level": {} }
+        }
+    }"#;
+
+    fn get_stars() -> Vec<Stars> {
+        vec![
+            Stars {
+                day: day!(2),
+                part_one: true,
+                part_two: true,
+                title: Some("Cube | Conundrum".into()),
+            },
+            Stars {
+                day: day!(10),
+                part_one: true,
+                part_two: false,
+                title: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn collects_stars_from_answers() {
+        let year = Year::new(2023).unwrap();
+        let mut answers = Answers::new();
+
+        for (year, day, part) in [
+            (2023, day!(10), Part::One),
+            (2023, day!(2), Part::Two),
+            (2023, day!(2), Part::One),
+            (2022, day!(1), Part::One),
+        ] {
+            let key = PuzzleKey {
+                year: Year::new(year).unwrap(),
+                day,
+                part,
+            };
+            answers.insert(key, "1".into());
+        }
+
+        let mut expected = get_stars();
+        expected[0].title = None;
+        assert_eq!(stars_from_answers(&answers, year), expected);
+    }
+
+    #[test]
+    fn collects_stars_from_leaderboard() {
+        let (year, stars) = stars_from_leaderboard(LEADERBOARD, None).unwrap();
+
+        let mut expected = get_stars();
+        expected[0].title = None;
+        assert_eq!(year, Year::new(2023));
+        assert_eq!(stars, expected);
+
+        let (_, stars) = stars_from_leaderboard(LEADERBOARD, Some("2")).unwrap();
+        assert!(stars.is_empty());
+
+        assert!(stars_from_leaderboard(LEADERBOARD, Some("3")).is_err());
+        assert!(stars_from_leaderboard("{", None).is_err());
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{MARKER}\n{MARKER}\nbaz");
+        let table = construct_table("##", Year::new(2023).unwrap(), &get_stars());
+        readme::replace_section(&mut s, MARKER, &table).unwrap();
+
+        let expected = [
+            "foo",
+            MARKER,
+            "## 2023 Results",
+            "",
+            "| Day | Title | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | Cube \\| Conundrum | ⭐ | ⭐ |",
+            "| [Day 10](https://adventofcode.com/2023/day/10) |  | ⭐ |   |",
+            MARKER,
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
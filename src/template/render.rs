@@ -0,0 +1,192 @@
+/// Renders puzzle descriptions (Markdown, possibly with inline HTML) for the terminal.
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::template::puzzle::{strip_tags, unescape_html, EMPHASIZED_CODE_PATTERN};
+use crate::template::{ANSI_BOLD, ANSI_DIM, ANSI_ITALIC, ANSI_RESET, ANSI_YELLOW};
+
+const CODE_INDENT: &str = "    ";
+
+/// Stands in for a rendered code span while the rest of a line is styled, e.g. `\0{0}\0`.
+const PLACEHOLDER: char = '\0';
+
+/// Styles inline elements of a line of text.
+fn render_inline(line: &str) -> String {
+    static HINT: OnceLock<Regex> = OnceLock::new();
+    static EMPHASIS: OnceLock<Regex> = OnceLock::new();
+    static CODE: OnceLock<Regex> = OnceLock::new();
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    static RESTORE: OnceLock<Regex> = OnceLock::new();
+
+    // emphasized code is how puzzles highlight answers, e.g. `*142*` or <code><em>142</em></code>.
+    let hint = HINT.get_or_init(|| Regex::new(EMPHASIZED_CODE_PATTERN).unwrap());
+    let emphasis = EMPHASIS.get_or_init(|| {
+        Regex::new(r"<em>(?P<a>[^<]+)</em>|\*\*(?P<b>[^*]+)\*\*|\*(?P<c>[^*\s][^*]*)\*").unwrap()
+    });
+    let code =
+        CODE.get_or_init(|| Regex::new(r"<code>(?P<a>[^<]+)</code>|`(?P<b>[^`]+)`").unwrap());
+    let link = LINK.get_or_init(|| Regex::new(r"\[(?P<text>[^\]]+)\]\([^)]*\)").unwrap());
+    let tag = TAG.get_or_init(|| Regex::new(r"</?[a-zA-Z][^>]*>").unwrap());
+    let restore = RESTORE.get_or_init(|| Regex::new(r"\x00(?P<index>\d+)\x00").unwrap());
+
+    let first_match = |captures: &regex::Captures| {
+        ["a", "b", "c", "d"]
+            .iter()
+            .find_map(|name| captures.name(name))
+            .map_or("", |x| x.as_str())
+            .to_string()
+    };
+
+    // links are resolved first, ANSI escape sequences contain brackets.
+    let line = link.replace_all(line, "$text");
+    let line = hint.replace_all(&line, |captures: &regex::Captures| {
+        format!(
+            "{ANSI_BOLD}{ANSI_YELLOW}{}{ANSI_RESET}",
+            first_match(captures)
+        )
+    });
+    // code spans are set aside, so that emphasis and tags inside them are shown as they are.
+    let mut spans = vec![];
+    let line = code.replace_all(&line, |captures: &regex::Captures| {
+        spans.push(format!(
+            "{ANSI_ITALIC}{}{ANSI_RESET}",
+            first_match(captures)
+        ));
+        format!("{PLACEHOLDER}{}{PLACEHOLDER}", spans.len() - 1)
+    });
+    let line = emphasis.replace_all(&line, |captures: &regex::Captures| {
+        format!("{ANSI_BOLD}{}{ANSI_RESET}", first_match(captures))
+    });
+    let line = tag.replace_all(&line, "");
+    let line = restore.replace_all(&line, |captures: &regex::Captures| {
+        spans[captures["index"].parse::<usize>().unwrap()].clone()
+    });
+
+    unescape_html(&line)
+}
+
+/// Renders a puzzle description with ANSI styling:
+///  1. headings are bold.
+///  2. code blocks, fenced or `<pre><code>`, are indented and dimmed.
+///  3. emphasis is bold, inline code italic, emphasized code (answer hints) bold and highlighted.
+#[must_use]
+pub fn render_markdown(description: &str) -> String {
+    let mut lines = vec![];
+    let mut in_code_block = false;
+    let mut in_html_block = false;
+
+    let code_line = |line: &str| format!("{CODE_INDENT}{ANSI_DIM}{line}{ANSI_RESET}");
+
+    for line in description.lines() {
+        let trimmed = line.trim();
+
+        if !in_html_block && trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            lines.push(code_line(line));
+            continue;
+        }
+
+        // the contents of html blocks may start on the line of `<pre><code>`, and end on the line of
+        // `</code></pre>`. Tags inside them, e.g. `<em>`, are dropped.
+        let mut content = line;
+
+        if !in_html_block {
+            if let Some(rest) = trimmed.strip_prefix("<pre><code>") {
+                in_html_block = true;
+                content = rest;
+            }
+        }
+
+        if in_html_block {
+            if let Some((before, _)) = content.split_once("</code></pre>") {
+                in_html_block = false;
+                content = before;
+                if content.is_empty() {
+                    continue;
+                }
+            }
+            lines.push(code_line(&unescape_html(&strip_tags(content))));
+            continue;
+        }
+
+        if let Some(heading) = trimmed.strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim();
+            lines.push(format!("{ANSI_BOLD}{}{ANSI_RESET}", render_inline(heading)));
+            continue;
+        }
+
+        lines.push(render_inline(line));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render_markdown;
+    use crate::template::{ANSI_BOLD, ANSI_DIM, ANSI_ITALIC, ANSI_RESET, ANSI_YELLOW};
+
+    #[test]
+    fn renders_headings() {
+        assert_eq!(
+            render_markdown("## --- Day 1: Trebuchet?! ---"),
+            format!("{ANSI_BOLD}--- Day 1: Trebuchet?! ---{ANSI_RESET}")
+        );
+    }
+
+    #[test]
+    fn renders_code_blocks() {
+        assert_eq!(
+            render_markdown("```\n1abc2\n*x*\n```"),
+            format!("    {ANSI_DIM}1abc2{ANSI_RESET}\n    {ANSI_DIM}*x*{ANSI_RESET}")
+        );
+    }
+
+    #[test]
+    fn renders_html_code_blocks() {
+        assert_eq!(
+            render_markdown("<pre><code>a &lt; <em>b</em>\n*c*\n</code></pre>\n<p>done</p>"),
+            format!("    {ANSI_DIM}a < b{ANSI_RESET}\n    {ANSI_DIM}*c*{ANSI_RESET}\ndone")
+        );
+        assert_eq!(
+            render_markdown("<pre><code>1abc2</code></pre>"),
+            format!("    {ANSI_DIM}1abc2{ANSI_RESET}")
+        );
+    }
+
+    #[test]
+    fn keeps_code_spans_verbatim() {
+        assert_eq!(
+            render_markdown("`2*3*4` and `a<b>c` are *not* the same"),
+            format!(
+                "{ANSI_ITALIC}2*3*4{ANSI_RESET} and {ANSI_ITALIC}a<b>c{ANSI_RESET} are {ANSI_BOLD}not{ANSI_RESET} the same"
+            )
+        );
+    }
+
+    #[test]
+    fn renders_inline_styles() {
+        assert_eq!(
+            render_markdown("The *calibration* value `12` sums to `*142*`, see [here](https://x.y)."),
+            format!(
+                "The {ANSI_BOLD}calibration{ANSI_RESET} value {ANSI_ITALIC}12{ANSI_RESET} sums to {ANSI_BOLD}{ANSI_YELLOW}142{ANSI_RESET}, see here."
+            )
+        );
+    }
+
+    #[test]
+    fn renders_html() {
+        assert_eq!(
+            render_markdown("<p>Total: <code><em>142</em></code> &lt; <em>a lot</em></p>"),
+            format!(
+                "Total: {ANSI_BOLD}{ANSI_YELLOW}142{ANSI_RESET} < {ANSI_BOLD}a lot{ANSI_RESET}"
+            )
+        );
+    }
+}
@@ -1,67 +1,474 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::allocator::{self, AllocStats};
+use crate::template::answers;
+use crate::template::config::config;
+use crate::template::crosscheck::{self, Variant};
+use crate::template::encryption;
+use crate::template::examples;
+use crate::template::inputs::{self, InputResult, Outcome};
+use crate::template::profiler::{self, Profile};
+use crate::template::readme_benchmarks;
+use crate::template::stable;
+use crate::template::{aoc_cli, cli, ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, Part, PuzzleKey, SystemClock};
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::AssertUnwindSafe;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::OnceLock;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, panic, process, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// If set, every step also prints its exact timing in a machine-readable line for `all` and
+/// `bench-compare`, see [`readme_benchmarks::format_record`].
+pub const RECORDS_ENV: &str = "AOC_TIMING_RECORDS";
+
+/// The arguments passed to a solution binary, see [`cli::SOLUTION`].
+pub struct SolutionArgs {
+    pub examples: bool,
+    pub time: bool,
+    /// Reduce benchmark noise, see [`stable`].
+    pub stable: bool,
+    /// Write a flamegraph of every step, see [`profiler`].
+    pub profile: bool,
+    /// The variant of the parts to run, see [`crate::solution`].
+    pub variant: Option<String>,
+    pub crosscheck: bool,
+    /// Run the parts on every named input of the day, see [`inputs`].
+    pub all_inputs: bool,
+    pub timeout: Option<Duration>,
+    pub submit: Option<Part>,
+}
+
+/// Parses the arguments of the solution binary once. Prints help or exits on invalid arguments.
+pub fn solution_args() -> &'static SolutionArgs {
+    static ARGS: OnceLock<SolutionArgs> = OnceLock::new();
+
+    ARGS.get_or_init(|| {
+        let raw: Vec<String> = env::args().skip(1).collect();
+
+        if cli::is_help(&raw) {
+            print!("{}", cli::format_help(&cli::SOLUTION, "cargo run --bin"));
+            process::exit(0);
+        }
+
+        parse_solution_args(raw).unwrap_or_else(|e| {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        })
+    })
+}
+
+fn parse_solution_args(raw: Vec<String>) -> Result<SolutionArgs, Box<dyn std::error::Error>> {
+    cli::validate(&cli::SOLUTION, &raw)?;
+
+    let mut args = pico_args::Arguments::from_vec(raw.into_iter().map(Into::into).collect());
+
+    let stable = args.contains(cli::STABLE.name);
+    let all_inputs = args.contains(cli::ALL_INPUTS.name);
+    let submit = args.opt_value_from_str(cli::SUBMIT.name)?;
+
+    if all_inputs && submit.is_some() {
+        return Err("--submit can not be combined with --all-inputs.".into());
+    }
+
+    Ok(SolutionArgs {
+        examples: args.contains(cli::EXAMPLES.name),
+        time: args.contains(cli::TIME.name) || stable,
+        stable,
+        profile: args.contains(cli::PROFILE.name),
+        variant: args.opt_value_from_str(cli::VARIANT.name)?,
+        crosscheck: args.contains(cli::CROSSCHECK.name),
+        all_inputs,
+        timeout: args
+            .opt_value_from_fn(cli::TIMEOUT.name, cli::parse_timeout)?
+            .map(Duration::from_secs),
+        submit,
+    })
+}
+
+/// The answer of a part and its execution time, the mean if it was benchmarked.
+pub type PartRun = (Option<String>, Duration);
+
+/// Run a part, print its answer and timing and submit it if requested.
+/// Returns `None` if the part timed out.
+pub fn run_part<I, T>(
+    func: impl Fn(I) -> Option<T> + Send + 'static,
+    input: I,
+    day: Day,
+    part: Part,
+) -> Option<PartRun>
+where
+    I: Clone + Send + 'static,
+    T: Display + Send + 'static,
+{
+    prepare_stable();
+
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let timeout = solution_args().timeout;
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let profile_name = format!("{day}-part-{part}");
+
+    let Some((result, measurement)) = run_timed(func, input, timeout, &profile_name, |result| {
+        print_result(result, &part_str, "");
+    }) else {
+        print_timeout(&part_str, timeout.unwrap_or_default());
+        return None;
+    };
+
+    print_result(&result, &part_str, &format_measurement(&measurement));
+    print_record(&part_str, &measurement);
+    print_profile(measurement.profile, &part_str);
+
+    let answer = result.as_ref().map(ToString::to_string);
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
+
+    Some((answer, measurement.duration))
+}
+
+/// Exits if `--variant` names a variant that neither part has. Otherwise, prints which variant runs.
+pub fn check_variant(variant: Option<&str>, part_one: &[&str], part_two: &[&str]) {
+    let Some(variant) = variant else {
+        return;
+    };
+
+    let (one, two) = (part_one.contains(&variant), part_two.contains(&variant));
+
+    if !one && !two {
+        let mut names: Vec<&str> = part_one.iter().chain(part_two).copied().collect();
+        names.sort_unstable();
+        names.dedup();
+
+        if names.is_empty() {
+            eprintln!("Unknown variant `{variant}`: this day has no variants.");
+        } else {
+            eprintln!(
+                "Unknown variant `{variant}`, expected one of: {}",
+                names.join(", ")
+            );
+        }
+
+        process::exit(1);
+    }
+
+    match (one, two) {
+        (true, false) => println!("Variant: {variant} (default implementation for part 2)"),
+        (false, true) => println!("Variant: {variant} (default implementation for part 1)"),
+        _ => println!("Variant: {variant}"),
+    }
+}
+
+/// Run every variant of the parts on the examples and the input and print where they agree.
+/// Exits with a non-zero status if any variants disagree.
+pub fn run_crosscheck(day: Day, part_one: &[Variant], part_two: &[Variant]) {
+    if part_one.len() < 2 && part_two.len() < 2 {
+        println!("Day {day} has no variants to cross-check.");
+        return;
+    }
+
+    let checks = match crosscheck::crosscheck(day, part_one, part_two) {
+        Ok(checks) => checks,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if checks.is_empty() {
+        println!("No examples or input to cross-check.");
+        return;
+    }
+
+    print!("{}", crosscheck::format_report(&checks));
+
+    if checks.iter().any(|x| !x.agrees()) {
+        process::exit(1);
+    }
+}
+
+/// Run the parts on every named input of the day and print a matrix of the answers and timings.
+/// `solve` runs both parts on an input. Exits with a non-zero status if a part gives a wrong answer,
+/// times out or panics on any input.
+///
+/// A step that timed out keeps running on its worker thread until the process exits, see [`run_timed`].
+/// It competes with the inputs after it for the CPU, so their timings are only indicative.
+pub fn run_all_inputs(
+    day: Day,
+    solve: impl Fn(&'static str) -> (Option<PartRun>, Option<PartRun>),
+) {
+    let named = match inputs::read_inputs(day) {
+        Ok(named) => named,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if named.is_empty() {
+        println!(
+            "No named inputs in {}.",
+            inputs::get_inputs_dir(day).display()
+        );
+        return;
+    }
+
+    let mut results: Vec<InputResult> = vec![];
+
+    for input in named {
+        println!("{ANSI_BOLD}{}{ANSI_RESET}", input.name);
+
+        let timed_out = results
+            .iter()
+            .any(|x| x.part_one == Outcome::TimedOut || x.part_two == Outcome::TimedOut);
+        if timed_out {
+            println!("{ANSI_ITALIC}(a step that timed out is still running, timings may be slower){ANSI_RESET}");
+        }
+
+        // parts run on worker threads that may outlive this input if they time out.
+        let text: &'static str = match encryption::read_input_file(&input.path) {
+            Ok(text) => text.leak(),
+            Err(e) => {
+                eprintln!("could not read \"{}\": {e}", input.path.display());
+                process::exit(1);
+            }
+        };
+
+        let outcome = |run: Option<PartRun>, expected: Option<String>| match run {
+            Some((answer, duration)) => Outcome::Answered {
+                answer,
+                expected,
+                duration,
+            },
+            None => Outcome::TimedOut,
+        };
+
+        // the panic message was already printed, move on to the next input.
+        let (part_one, part_two) = match panic::catch_unwind(AssertUnwindSafe(|| solve(text))) {
+            Ok((one, two)) => (outcome(one, input.part_one), outcome(two, input.part_two)),
+            Err(_) => (Outcome::Panic, Outcome::Panic),
+        };
+
+        results.push(InputResult {
+            name: input.name,
+            part_one,
+            part_two,
+        });
+        println!();
+    }
+
+    println!("{}", inputs::format_matrix(&results));
+
+    if results
+        .iter()
+        .any(|x| x.part_one.is_failure() || x.part_two.is_failure())
+    {
+        process::exit(1);
+    }
+}
+
+/// Check the parts against the example manifest of the day and print a result matrix.
+/// Exits with a non-zero status if any example fails.
+pub fn check_examples<T1: Display, T2: Display>(
+    day: Day,
+    part_one: impl Fn(&str) -> Option<T1>,
+    part_two: impl Fn(&str) -> Option<T2>,
+) {
+    let results = match examples::run_examples(day, part_one, part_two) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if results.is_empty() {
+        println!(
+            "No examples in {}.",
+            examples::get_manifest_path(day).display()
+        );
+        return;
+    }
+
+    println!("{}", examples::format_matrix(&results));
+
+    if results
+        .iter()
+        .any(|x| x.part_one.is_failure() || x.part_two.is_failure())
+    {
+        process::exit(1);
+    }
+}
+
+/// Run the shared parse step of a solution and return the parsed value for the parts.
+/// Exits the process if parsing exceeds the time budget, as the parts cannot run without it.
+pub fn run_parse<I, P>(func: impl Fn(I) -> P + Send + 'static, input: I, day: Day) -> P
+where
+    I: Clone + Send + 'static,
+    P: Send + 'static,
+{
+    try_run_parse(func, input, day).unwrap_or_else(|| process::exit(1))
+}
+
+/// Run the shared parse step of a solution like [`run_parse`], but return `None` if it timed out.
+pub fn try_run_parse<I, P>(func: impl Fn(I) -> P + Send + 'static, input: I, day: Day) -> Option<P>
+where
+    I: Clone + Send + 'static,
+    P: Send + 'static,
+{
+    prepare_stable();
+
+    let timeout = solution_args().timeout;
+
+    let profile_name = format!("{day}-parse");
+
+    let Some((parsed, measurement)) = run_timed(func, input, timeout, &profile_name, |_| {
+        print!("Parse:");
+    }) else {
+        print_timeout("Parse", timeout.unwrap_or_default());
+        return None;
+    };
+
+    print!("\r");
+    println!("Parse:{}", format_measurement(&measurement));
+    print_record("Parse", &measurement);
+    print_profile(measurement.profile, "Parse");
+
+    Some(parsed)
+}
+
+/// Applies the settings of `--stable` once, before the first step runs, and prints them so that
+/// `all` can record them alongside the timings.
+fn prepare_stable() {
+    static APPLIED: OnceLock<()> = OnceLock::new();
+
+    if !solution_args().stable {
+        return;
+    }
+
+    APPLIED.get_or_init(|| {
+        // parts run on threads spawned by this one, which inherit the affinity and priority.
+        let settings = stable::apply(config().bench.core);
+        println!("Stable: {settings}");
+    });
+}
+
+/// Timing information (and allocations, if tracked) of a solution part.
+struct Measurement {
+    /// Mean duration of the samples.
+    duration: Duration,
+    median: Duration,
+    stddev: Duration,
+    samples: u128,
+    allocations: Option<AllocStats>,
+    /// Set if `--profile` was passed.
+    profile: Option<Result<Profile, profiler::Error>>,
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+///     The bounds can be changed in the `[bench]` section of the project config.
+///     With `--stable`, every sample is preceded by an untimed warm-up run.
+///     If the counting allocator is enabled, one additional run records the allocations.
+///
+/// With `--profile`, the step is also sampled by the [`profiler`] and its flamegraph is written to `profile_name`.
+///
+/// The first execution happens on a worker thread. If it does not finish within `timeout`, the worker is
+/// abandoned and `None` is returned. Threads cannot be killed, so the worker keeps running until the
+/// process exits and slows down the steps after it.
+fn run_timed<I, T, F>(
+    func: F,
     input: I,
+    timeout: Option<Duration>,
+    profile_name: &str,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+) -> Option<(T, Measurement)>
+where
+    I: Clone + Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> T + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+
+    let worker = thread::spawn(move || {
+        let timer = Instant::now();
+        let result = func(input.clone());
+        let base_time = timer.elapsed();
+        // the receiver is gone if the part timed out, nobody is interested in the result anymore.
+        let _ = tx.send((result, base_time));
+        (func, input)
+    });
+
+    let received = match timeout {
+        Some(timeout) => rx.recv_timeout(timeout),
+        None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+
+    let (result, base_time) = match received {
+        Ok(received) => received,
+        Err(RecvTimeoutError::Timeout) => return None,
+        // the worker hung up without sending a result, i.e. the solution panicked.
+        Err(RecvTimeoutError::Disconnected) => match worker.join() {
+            Ok(_) => unreachable!("worker exited without sending a result"),
+            Err(e) => panic::resume_unwind(e),
+        },
+    };
+
+    let (func, input) = match worker.join() {
+        Ok(worker) => worker,
+        Err(e) => panic::resume_unwind(e),
+    };
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let profile = solution_args().profile.then(|| {
+        print!(" > {ANSI_ITALIC}profiling{ANSI_RESET}");
+        let _ = stdout().flush();
+        profiler::profile(&func, &input, profile_name)
+    });
+
+    let mut measurement = if solution_args().time {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        Measurement {
+            duration: base_time,
+            median: base_time,
+            stddev: Duration::ZERO,
+            samples: 1,
+            allocations: None,
+            profile: None,
+        }
     };
 
-    (result, run.0, run.1)
+    measurement.profile = profile;
+
+    Some((result, measurement))
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Measurement {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bounds = &config().bench;
+    let bench_iterations = (bounds.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(bounds.min_samples, bounds.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
+    let warm_up = solution_args().stable;
 
     for _ in 0..bench_iterations {
+        if warm_up {
+            func(input.clone());
+        }
+
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
@@ -69,11 +476,19 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
+    let allocations = allocator::is_enabled().then(|| allocator::measure(|| func(input)).1);
+
+    let average = average_duration(&timers);
+
+    Measurement {
         #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+        duration: Duration::from_nanos(average as u64),
+        median: median_duration(&mut timers),
+        stddev: stddev_duration(&timers, average),
+        samples: bench_iterations,
+        allocations,
+        profile: None,
+    }
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -84,14 +499,79 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
+fn median_duration(numbers: &mut [Duration]) -> Duration {
+    numbers.sort_unstable();
+    numbers[numbers.len() / 2]
+}
+
+/// Standard deviation of the samples around their mean.
+#[allow(clippy::cast_precision_loss)]
+fn stddev_duration(numbers: &[Duration], average: u128) -> Duration {
+    let variance = numbers
+        .iter()
+        .map(|x| (x.as_nanos() as f64 - average as f64).powi(2))
+        .sum::<f64>()
+        / numbers.len() as f64;
+
+    Duration::from_secs_f64(variance.sqrt() / 1e9)
+}
+
+fn format_measurement(measurement: &Measurement) -> String {
+    let Measurement {
+        duration,
+        median,
+        stddev,
+        samples,
+        allocations,
+        ..
+    } = measurement;
+
+    let timing = if *samples == 1 {
         format!(" ({duration:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({duration:.1?} ± {stddev:.1?}, median {median:.1?} @ {samples} samples)")
+    };
+
+    match allocations {
+        Some(allocations) => format!("{timing} [{allocations}]"),
+        None => timing,
+    }
+}
+
+/// Prints the exact timing of a step if requested with [`RECORDS_ENV`].
+fn print_record(step: &str, measurement: &Measurement) {
+    if env::var_os(RECORDS_ENV).is_none() {
+        return;
+    }
+
+    let record = readme_benchmarks::Measurement {
+        mean: measurement.duration,
+        median: Some(measurement.median),
+        stddev: Some(measurement.stddev),
+        samples: measurement.samples,
+        allocs: measurement.allocations,
+    };
+
+    println!("{}", readme_benchmarks::format_record(step, &record));
+}
+
+fn print_profile(profile: Option<Result<Profile, profiler::Error>>, part: &str) {
+    match profile {
+        Some(Ok(profile)) => println!(
+            "{part}: {} samples in \"{}\", folded stacks in \"{}\"",
+            profile.samples,
+            profile.flamegraph.display(),
+            profile.folded.display()
+        ),
+        Some(Err(e)) => eprintln!("{part}: {e}"),
+        None => {}
     }
 }
 
+fn print_timeout(part: &str, timeout: Duration) {
+    println!("{part}: TIMEOUT after {timeout:?}");
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -127,41 +607,37 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
+/// Try to submit one part of the solution if:
+///  1. it is the part passed via `--submit`.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
-        return None;
+///
+/// Accepted answers are recorded in the answers file, see [`answers`].
+fn submit_result<T: Display>(result: T, day: Day, part: Part) {
+    if solution_args().submit != Some(part) {
+        return;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
-
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let key = PuzzleKey {
+        year: config().year_or_current(&SystemClock),
+        day,
+        part,
     };
 
-    if part_submit != part {
-        return None;
-    }
+    let result = result.to_string();
+    println!("Submitting result for {key} via aoc-cli...");
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    match aoc_cli::submit(key, &result) {
+        Ok(output) if aoc_cli::is_accepted(&output) => {
+            if let Err(e) = answers::record_answer(key, &result) {
+                eprintln!("Failed to record answer: {e}");
+            }
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Failed to submit result: {e}"),
     }
-
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
 }
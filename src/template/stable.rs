@@ -0,0 +1,123 @@
+/// Noise reduction for `--stable` benchmarks. The benchmark thread is pinned to a single core and its
+/// priority is raised, where the OS permits it. Threads spawned afterwards inherit both settings.
+use std::fmt::Display;
+use std::{io, thread};
+
+/// The nice value requested for the benchmark thread. Lower values need privileges on most systems.
+pub const NICE: i32 = -10;
+
+/// The settings that were applied, recorded alongside the timings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    /// The core the thread was pinned to, `None` if pinning failed or is not supported.
+    pub core: Option<usize>,
+    /// The nice value of the thread, `None` if the priority could not be raised.
+    pub nice: Option<i32>,
+}
+
+impl Display for Settings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.core {
+            Some(core) => write!(f, "core {core}")?,
+            None => write!(f, "unpinned")?,
+        }
+
+        match self.nice {
+            Some(nice) => write!(f, ", nice {nice}")?,
+            None => write!(f, ", default priority")?,
+        }
+
+        write!(f, ", interleaved warm-up")
+    }
+}
+
+/// The last core is the least likely to also handle interrupts and cargo's own processes.
+#[must_use]
+pub fn default_core() -> usize {
+    thread::available_parallelism().map_or(0, |x| x.get() - 1)
+}
+
+/// Pins the calling thread to `core` and raises its priority. Failures are reported as warnings,
+/// benchmarks still run without the setting.
+#[must_use]
+pub fn apply(core: Option<usize>) -> Settings {
+    let core = core.unwrap_or_else(default_core);
+
+    Settings {
+        core: pin_to_core(core)
+            .map(|()| core)
+            .map_err(|e| eprintln!("Could not pin benchmarks to core {core}: {e}"))
+            .ok(),
+        nice: raise_priority(NICE)
+            .map(|()| NICE)
+            .map_err(|e| eprintln!("Could not raise benchmark priority: {e}"))
+            .ok(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn pin_to_core(core: usize) -> io::Result<()> {
+    // `CPU_SET` panics for cores that do not fit into the set.
+    if core >= libc::CPU_SETSIZE as usize {
+        return Err(io::Error::from(io::ErrorKind::InvalidInput));
+    }
+
+    // SAFETY: `cpu_set_t` is a plain bit set, and the core was checked to be in range.
+    let result = unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(core, &mut set);
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set)
+    };
+
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn raise_priority(nice: i32) -> io::Result<()> {
+    // on Linux, this only affects the calling thread (and threads spawned by it).
+    // SAFETY: plain syscall without pointers.
+    let result = unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, nice) };
+
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_to_core(_core: usize) -> io::Result<()> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn raise_priority(_nice: i32) -> io::Result<()> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Settings;
+
+    #[test]
+    fn formats_settings() {
+        let applied = Settings {
+            core: Some(3),
+            nice: Some(-10),
+        };
+        assert_eq!(applied.to_string(), "core 3, nice -10, interleaved warm-up");
+
+        let failed = Settings {
+            core: None,
+            nice: None,
+        };
+        assert_eq!(
+            failed.to_string(),
+            "unpinned, default priority, interleaved warm-up"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+//! Plain text tables with aligned columns, as printed for named inputs, examples and `bench-compare`.
+
+/// Formats a header and rows as lines of cells padded to the width of their column.
+/// Cells are joined by `separator`, trailing whitespace is trimmed.
+#[must_use]
+pub fn format_columns<const N: usize>(
+    header: [&str; N],
+    rows: &[[String; N]],
+    separator: &str,
+) -> String {
+    let mut widths = header.map(|x| x.chars().count());
+
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |row: &[&str]| {
+        row.iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join(separator)
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![format_row(&header)];
+    lines.extend(
+        rows.iter()
+            .map(|row| format_row(&row.each_ref().map(String::as_str))),
+    );
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_columns;
+
+    #[test]
+    fn aligns_columns() {
+        let rows = [
+            ["alice".to_string(), "✔ 142".into(), String::new()],
+            ["bob".to_string(), "✖".into(), "281".into()],
+        ];
+
+        assert_eq!(
+            format_columns(["Input", "Part 1", "Part 2"], &rows, " | "),
+            "Input | Part 1 | Part 2\nalice | ✔ 142  |\nbob   | ✖      | 281"
+        );
+    }
+}
@@ -0,0 +1,271 @@
+/// Templates used by `scaffold` to create the solution module and example manifest of a new day.
+///
+/// Project templates are looked up by name in `.aoc/templates`: `NAME.rs.tpl` for the module and,
+/// optionally, `NAME.toml.tpl` for the example manifest. A project template named `default` replaces
+/// the built-in one. Templates may use the following placeholders:
+///
+/// | Placeholder         | Value                                               |
+/// | ------------------- | --------------------------------------------------- |
+/// | `{{day}}`           | the day number, e.g. `7`                            |
+/// | `{{day_padded}}`    | the zero-padded day, e.g. `07`                      |
+/// | `{{year}}`          | the configured year, e.g. `2023`                    |
+/// | `{{title}}`         | the puzzle title, if the description was downloaded |
+/// | `{{part_one_type}}` | the return type of part one, `u32` by default       |
+/// | `{{part_two_type}}` | the return type of part two, `u32` by default       |
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use crate::{Day, Year};
+
+pub const TEMPLATE_DIR: &str = ".aoc/templates";
+pub const DEFAULT_TEMPLATE: &str = "default";
+pub const DEFAULT_RETURN_TYPE: &str = "u32";
+
+const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!({{day}});
+
+pub fn part_one(input: &str) -> Option<{{part_one_type}}> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{part_two_type}}> {
+    None
+}
+"#;
+
+const MANIFEST_TEMPLATE: &str = r#"# Expected answers for the example inputs, checked by `cargo test --bin {{day_padded}}`.
+[[example]]
+file = "{{day_padded}}.txt"
+# part_one = 0
+# part_two = 0
+"#;
+
+#[derive(Debug)]
+pub enum Error {
+    NotFound {
+        name: String,
+        available: Vec<String>,
+    },
+    Placeholder(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NotFound { name, available } if available.is_empty() => {
+                write!(f, "template \"{name}\" not found in {TEMPLATE_DIR}")
+            }
+            Error::NotFound { name, available } => write!(
+                f,
+                "template \"{name}\" not found in {TEMPLATE_DIR}, available: {}",
+                available.join(", ")
+            ),
+            Error::Placeholder(name) => {
+                write!(f, "unknown template placeholder \"{{{{{name}}}}}\"")
+            }
+            Error::IO(e) => write!(f, "could not read template: {e}"),
+        }
+    }
+}
+
+/// The module and manifest template of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    pub module: String,
+    pub manifest: String,
+}
+
+/// The values substituted for the placeholders of a template.
+#[derive(Debug, Clone)]
+pub struct Context {
+    pub day: Day,
+    pub year: Year,
+    pub title: Option<String>,
+    pub part_one_type: String,
+    pub part_two_type: String,
+}
+
+impl Context {
+    fn get(&self, placeholder: &str) -> Option<String> {
+        match placeholder {
+            "day" => Some(self.day.into_inner().to_string()),
+            "day_padded" => Some(self.day.to_string()),
+            "year" => Some(self.year.to_string()),
+            "title" => Some(self.title.clone().unwrap_or_default()),
+            "part_one_type" => Some(self.part_one_type.clone()),
+            "part_two_type" => Some(self.part_two_type.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl Template {
+    fn builtin() -> Self {
+        Self {
+            module: MODULE_TEMPLATE.into(),
+            manifest: MANIFEST_TEMPLATE.into(),
+        }
+    }
+
+    /// Loads a template by name from the project templates.
+    /// Without a name, the project's `default` template is used if present, the built-in one otherwise.
+    pub fn load(name: Option<&str>) -> Result<Self, Error> {
+        let dir = PathBuf::from(TEMPLATE_DIR);
+        let module_path = dir.join(format!("{}.rs.tpl", name.unwrap_or(DEFAULT_TEMPLATE)));
+
+        if !module_path.exists() {
+            return match name {
+                None => Ok(Self::builtin()),
+                Some(name) => Err(Error::NotFound {
+                    name: name.into(),
+                    available: list_templates(&dir)?,
+                }),
+            };
+        }
+
+        let manifest_path = module_path.with_extension("").with_extension("toml.tpl");
+
+        Ok(Self {
+            module: fs::read_to_string(&module_path)?,
+            manifest: if manifest_path.exists() {
+                fs::read_to_string(&manifest_path)?
+            } else {
+                MANIFEST_TEMPLATE.into()
+            },
+        })
+    }
+}
+
+/// Lists the names of the module templates in a directory.
+fn list_templates(dir: &Path) -> Result<Vec<String>, Error> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut names: Vec<String> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let file_name = entry.file_name().into_string().ok()?;
+            file_name.strip_suffix(".rs.tpl").map(String::from)
+        })
+        .collect();
+
+    names.sort();
+    Ok(names)
+}
+
+/// Replaces all `{{placeholder}}` occurrences of a template with their values.
+/// Braces around anything but a name, e.g. `format!("{{}}")`, are kept as they are. `\{{` is rendered
+/// as `{{`, for braces around a name like `format!("\{{x}}")`.
+pub fn render(template: &str, context: &Context) -> Result<String, Error> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        if rest[..start].ends_with('\\') {
+            rendered.push_str(&rest[..start - 1]);
+            rendered.push_str("{{");
+            rest = &rest[start + 2..];
+            continue;
+        }
+
+        let inner = &rest[start + 2..];
+        let placeholder = inner
+            .find("}}")
+            .map(|len| (inner[..len].trim(), len))
+            .filter(|(name, _)| is_placeholder_name(name));
+
+        let Some((placeholder, len)) = placeholder else {
+            rendered.push_str(&rest[..start + 2]);
+            rest = inner;
+            continue;
+        };
+
+        let value = context
+            .get(placeholder)
+            .ok_or_else(|| Error::Placeholder(placeholder.into()))?;
+
+        rendered.push_str(&rest[..start]);
+        rendered.push_str(&value);
+        rest = &inner[len + 2..];
+    }
+
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+fn is_placeholder_name(s: &str) -> bool {
+    s.starts_with(|x: char| x.is_ascii_lowercase() || x == '_')
+        && s.chars()
+            .all(|x| x.is_ascii_lowercase() || x.is_ascii_digit() || x == '_')
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Context, Error, Template};
+    use crate::{day, Year};
+
+    fn get_context() -> Context {
+        Context {
+            day: day!(7),
+            year: Year::new(2023).unwrap(),
+            title: Some("Camel Cards".into()),
+            part_one_type: "u64".into(),
+            part_two_type: "String".into(),
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let rendered = render(
+            "// {{year}} day {{ day }}: {{title}}\nfn a() -> {{part_one_type}} {}\nfn b() -> {{part_two_type}} {}\n// {{day_padded}}.txt",
+            &get_context(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            rendered,
+            "// 2023 day 7: Camel Cards\nfn a() -> u64 {}\nfn b() -> String {}\n// 07.txt"
+        );
+    }
+
+    #[test]
+    fn renders_builtin_template() {
+        let template = Template::builtin();
+        let module = render(&template.module, &get_context()).unwrap();
+        let manifest = render(&template.manifest, &get_context()).unwrap();
+
+        assert!(module.starts_with("advent_of_code::solution!(7);"));
+        assert!(module.contains("Option<String>"));
+        assert!(manifest.contains("file = \"07.txt\""));
+    }
+
+    #[test]
+    fn keeps_escaped_braces() {
+        let rendered = render(
+            "println!(\"{{}} {{:?}} {{ {} }}\", {{day}});\nlet s = format!(\"\\{{x}}\");",
+            &get_context(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            rendered,
+            "println!(\"{{}} {{:?}} {{ {} }}\", 7);\nlet s = format!(\"{{x}}\");"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_placeholders() {
+        assert!(matches!(
+            render("{{day}} {{month}}", &get_context()),
+            Err(Error::Placeholder(x)) if x == "month"
+        ));
+    }
+}
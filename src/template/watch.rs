@@ -0,0 +1,99 @@
+/// Polls the files of a day for modifications, used by the `--watch` flags.
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::template::config::config;
+use crate::template::encryption;
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::Day;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Editors often write files in several steps, wait for them to settle before re-running.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+type Snapshot = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// Returns `true` if a file in the data folders belongs to `day`, e.g. `08.txt` or `08-1.txt` for day 8.
+fn is_file_for_day(file_name: &str, day: Day) -> bool {
+    let day = day.to_string();
+
+    file_name
+        .strip_prefix(&day)
+        .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('-'))
+}
+
+/// The day module, its example files and its input.
+fn files_for_day(day: Day) -> Vec<PathBuf> {
+    let mut files = vec![
+        PathBuf::from(format!("src/bin/{day}.rs")),
+        config().inputs_dir().join(format!("{day}.txt")),
+        encryption::get_encrypted_path(&config().inputs_dir().join(format!("{day}.txt"))),
+    ];
+
+    if let Ok(entries) = fs::read_dir(config().examples_dir()) {
+        let mut examples: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .filter(|entry| is_file_for_day(&entry.file_name().to_string_lossy(), day))
+            .map(|entry| entry.path())
+            .collect();
+        examples.sort();
+        files.append(&mut examples);
+    }
+
+    files
+}
+
+fn snapshot(day: Day) -> Snapshot {
+    files_for_day(day)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|x| x.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Runs `on_change` once, then again every time one of the files of `day` is created, modified or removed.
+/// Never returns, stop with Ctrl-C.
+pub fn watch_day(day: Day, mut on_change: impl FnMut()) -> ! {
+    let mut last = snapshot(day);
+
+    loop {
+        print!("{ANSI_CLEAR_SCREEN}");
+        println!("{ANSI_BOLD}👀 Watching day {day} for changes (Ctrl-C to stop)...{ANSI_RESET}");
+        println!();
+
+        on_change();
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let current = snapshot(day);
+
+            if current != last {
+                thread::sleep(DEBOUNCE);
+                last = snapshot(day);
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::is_file_for_day;
+    use crate::day;
+
+    #[test]
+    fn matches_files_for_day() {
+        assert!(is_file_for_day("08.txt", day!(8)));
+        assert!(is_file_for_day("08-1.txt", day!(8)));
+        assert!(is_file_for_day("08.toml", day!(8)));
+        assert!(!is_file_for_day("18.txt", day!(8)));
+        assert!(!is_file_for_day("081.txt", day!(8)));
+        assert!(!is_file_for_day("08", day!(8)));
+    }
+}
@@ -0,0 +1,120 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::clock::{puzzle_date, Clock};
+
+/// The first year advent of code took place.
+const FIRST_YEAR: u16 = 2015;
+
+/// A year of advent of code (i.e. 2015 or later).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if advent of code took place in that year,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Reads the year from the `AOC_YEAR` environment variable.
+    pub fn from_env() -> Option<Self> {
+        std::env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// The most recent year whose advent has started.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn current(clock: &impl Clock) -> Self {
+        let (year, month, _) = puzzle_date(clock.now());
+        let year = if month == 12 { year } else { year - 1 };
+        Self((year as u16).max(FIRST_YEAR))
+    }
+
+    /// The year of the advent that is running or comes next, i.e. the current year from
+    /// January on, unlike [`Year::current`].
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn upcoming(clock: &impl Clock) -> Self {
+        let (year, _, _) = puzzle_date(clock.now());
+        Self((year as u16).max(FIRST_YEAR))
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+    use crate::FixedClock;
+
+    #[test]
+    fn current_year() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(
+            Year::current(&FixedClock::from_unix_secs(1_701_406_800)),
+            Year(2023)
+        );
+        // 2023-12-01T04:59:59Z, advent 2023 has not started yet.
+        assert_eq!(
+            Year::current(&FixedClock::from_unix_secs(1_701_406_799)),
+            Year(2022)
+        );
+    }
+
+    #[test]
+    fn upcoming_year() {
+        // 2023-12-01T04:59:59Z, advent 2023 starts in a second.
+        assert_eq!(
+            Year::upcoming(&FixedClock::from_unix_secs(1_701_406_799)),
+            Year(2023)
+        );
+        // 2024-01-01T05:00:00Z
+        assert_eq!(
+            Year::upcoming(&FixedClock::from_unix_secs(1_704_085_200)),
+            Year(2024)
+        );
+    }
+
+    #[test]
+    fn parses_year() {
+        assert_eq!("2023".parse::<Year>().unwrap(), Year(2023));
+        assert!("2014".parse::<Year>().is_err());
+    }
+}